@@ -0,0 +1,225 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use nom::{
+    IResult, Parser,
+    branch::alt,
+    bytes::complete::{is_not, tag},
+    character::complete::{char, i64, line_ending, not_line_ending, space0, u8, u64},
+    combinator::{map, opt, value},
+    multi::many0,
+    sequence::{delimited, preceded, terminated},
+};
+
+use crate::error::{Error, Result, parse_all};
+use crate::solution::{Answer, Answers};
+
+/// Name of the answers file in the resources directory.
+pub const ANSWERS_FILE: &str = "answers.toml";
+
+/// Recorded answers of the real inputs, by day.
+///
+/// The file is a small subset of TOML:
+/// ```toml
+/// # comment
+/// [day1] # comment
+/// part1 = 1234
+/// part2 = "text answer"
+/// ```
+pub type RecordedAnswers = BTreeMap<u8, Answers>;
+
+pub fn answers_path(resources_dir: &Path) -> PathBuf {
+    resources_dir.join(ANSWERS_FILE)
+}
+
+/// Result of the comparison of one part with its recorded answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Check {
+    Match,
+    Mismatch {
+        expected: Answer,
+        actual: Answer,
+    },
+    /// The part was recorded but not computed
+    Missing {
+        expected: Answer,
+    },
+}
+
+/// Compares the computed answers with the recorded ones, part by part.
+///
+/// Answers are compared through their text so that `5` matches both signed and unsigned answers.
+pub fn check(expected: &Answers, actual: &Answers) -> Vec<(u8, Check)> {
+    [
+        (1, &expected.part1, &actual.part1),
+        (2, &expected.part2, &actual.part2),
+    ]
+    .into_iter()
+    .filter_map(|(part, expected, actual)| {
+        let expected = expected.clone()?;
+        let check = match actual {
+            Some(actual) if actual.to_string() == expected.to_string() => Check::Match,
+            Some(actual) => Check::Mismatch {
+                expected,
+                actual: actual.clone(),
+            },
+            None => Check::Missing { expected },
+        };
+        Some((part, check))
+    })
+    .collect()
+}
+
+// === PARSERS ===
+
+/// Spaces up to the end of the line, with an optional comment.
+fn line_end(input: &str) -> IResult<&str, ()> {
+    value((), (space0, opt(preceded(char('#'), not_line_ending)))).parse(input)
+}
+
+fn blank(input: &str) -> IResult<&str, ()> {
+    value((), (line_end, line_ending)).parse(input)
+}
+
+fn header(input: &str) -> IResult<&str, u8> {
+    delimited((space0, tag("[day")), u8, (char(']'), line_end)).parse(input)
+}
+
+fn answer(input: &str) -> IResult<&str, Answer> {
+    alt((
+        map(u64, Answer::Unsigned),
+        map(i64, Answer::Signed),
+        map(
+            delimited(char('"'), opt(is_not("\"\n")), char('"')),
+            |s: Option<&str>| Answer::Text(s.unwrap_or_default().to_string()),
+        ),
+    ))
+    .parse(input)
+}
+
+fn entry(input: &str) -> IResult<&str, (u8, Answer)> {
+    let (remainder, (_, _, part, _, answer, _)) = (
+        space0,
+        tag("part"),
+        alt((value(1, char('1')), value(2, char('2')))),
+        (space0, char('='), space0),
+        answer,
+        line_end,
+    )
+        .parse(input)?;
+    Ok((remainder, (part, answer)))
+}
+
+fn section(input: &str) -> IResult<&str, (u8, Answers)> {
+    let (remainder, day) = terminated(header, opt(line_ending)).parse(input)?;
+    let (remainder, entries) =
+        many0(preceded(many0(blank), terminated(entry, opt(line_ending)))).parse(remainder)?;
+    let mut answers = Answers::default();
+    entries.into_iter().for_each(|(part, answer)| match part {
+        1 => answers.part1 = Some(answer),
+        _ => answers.part2 = Some(answer),
+    });
+    Ok((remainder, (day, answers)))
+}
+
+pub fn sections(input: &str) -> IResult<&str, Vec<(u8, Answers)>> {
+    preceded(many0(blank), many0(terminated(section, many0(blank)))).parse(input)
+}
+
+/// Parses the answers file, a day being recorded at most once.
+pub fn recorded_answers(input: &str) -> Result<RecordedAnswers> {
+    let mut recorded = RecordedAnswers::new();
+    for (day, answers) in parse_all(input, sections)? {
+        if recorded.insert(day, answers).is_some() {
+            return Err(Error::InvalidInput(format!(
+                "the answers of day {day} are recorded twice"
+            )));
+        }
+    }
+    Ok(recorded)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::error::{Error, Result, parse_all};
+    use crate::solution::{Answer, Answers};
+
+    use super::{Check, check, recorded_answers};
+
+    #[test]
+    fn parsers() {
+        let data = "# answers of the real inputs
+
+[day1]
+part1 = 1102
+part2 = 6175 # second star
+
+[day8]
+part2 = -25272
+[day12]
+part1 = \"abc\"
+";
+        let answers = recorded_answers(data).expect("Could not parse the answers");
+        assert_eq!(answers.len(), 3);
+        assert_eq!(answers[&1].part1, Some(Answer::Unsigned(1102)));
+        assert_eq!(answers[&1].part2, Some(Answer::Unsigned(6175)));
+        assert_eq!(answers[&8].part1, None);
+        assert_eq!(answers[&8].part2, Some(Answer::Signed(-25272)));
+        assert_eq!(answers[&12].part1, Some(Answer::Text("abc".to_string())));
+
+        assert!(recorded_answers("").expect("empty file").is_empty());
+        assert!(recorded_answers("[day1]\npart3 = 4\n").is_err());
+    }
+
+    #[test]
+    fn header_comment() {
+        let answers = recorded_answers("[day5] # fresh ids\npart1 = 3\n")
+            .expect("Could not parse the answers");
+        assert_eq!(answers[&5].part1, Some(Answer::Unsigned(3)));
+    }
+
+    #[test]
+    fn duplicate_section() {
+        let err = recorded_answers("[day5]\npart1 = 3\n\n[day5]\npart2 = 14\n")
+            .expect_err("day 5 is recorded twice");
+        assert_eq!(
+            err.to_string(),
+            "invalid input : the answers of day 5 are recorded twice"
+        );
+    }
+
+    #[test]
+    fn checks() {
+        let expected = Answers {
+            part1: Some(Answer::Unsigned(40)),
+            part2: Some(Answer::Unsigned(25272)),
+        };
+        let actual = Answers {
+            part1: Some(Answer::Unsigned(41)),
+            part2: Some(Answer::Signed(25272)),
+        };
+        assert_eq!(
+            check(&expected, &actual),
+            vec![
+                (
+                    1,
+                    Check::Mismatch {
+                        expected: Answer::Unsigned(40),
+                        actual: Answer::Unsigned(41)
+                    }
+                ),
+                (2, Check::Match)
+            ]
+        );
+        assert_eq!(
+            check(&expected, &Answers::default())[0],
+            (
+                1,
+                Check::Missing {
+                    expected: Answer::Unsigned(40)
+                }
+            )
+        );
+    }
+}
@@ -0,0 +1,101 @@
+use std::fmt::Display;
+use std::time::Duration;
+
+/// Durations measured over repeated runs of a day.
+#[derive(Debug, Clone, Default)]
+pub struct Samples {
+    pub parse: Vec<Duration>,
+    pub part1: Vec<Duration>,
+    pub part2: Vec<Duration>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let n = sorted.len();
+        let median = match n {
+            0 => return None,
+            _ if n % 2 == 1 => sorted[n / 2],
+            _ => (sorted[n / 2 - 1] + sorted[n / 2]) / 2,
+        };
+        Some(Stats {
+            min: sorted[0],
+            median,
+            max: sorted[n - 1],
+        })
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "min {:>10.2?} | median {:>10.2?} | max {:>10.2?}",
+            self.min, self.median, self.max
+        )
+    }
+}
+
+/// Renders the min/median/max of every measured step of a day.
+pub fn report(day: u8, samples: &Samples) -> String {
+    let mut lines = vec![format!("Day {day} ({} runs)", samples.parse.len())];
+    [
+        ("parse", &samples.parse),
+        ("part 1", &samples.part1),
+        ("part 2", &samples.part2),
+    ]
+    .iter()
+    .for_each(|(name, durations)| {
+        if let Some(stats) = Stats::from_samples(durations) {
+            lines.push(format!("  {name:<6} : {stats}"));
+        }
+    });
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::time::Duration;
+
+    use crate::day1::Day1;
+    use crate::solution::{DynSolution, Part};
+
+    use super::Stats;
+
+    #[test]
+    fn stats() {
+        let ms = Duration::from_millis;
+        assert_eq!(Stats::from_samples(&[]), None);
+        assert_eq!(
+            Stats::from_samples(&[ms(5), ms(1), ms(3)]),
+            Some(Stats {
+                min: ms(1),
+                median: ms(3),
+                max: ms(5)
+            })
+        );
+        assert_eq!(
+            Stats::from_samples(&[ms(4), ms(1), ms(2), ms(8)]).map(|s| s.median),
+            Some(ms(3))
+        );
+    }
+
+    #[test]
+    fn samples() {
+        let samples = Day1
+            .bench("L68\nL30\nR48", Part::Two, 3)
+            .expect("Could not bench day 1");
+        assert_eq!(samples.parse.len(), 3);
+        assert!(samples.part1.is_empty());
+        assert_eq!(samples.part2.len(), 3);
+    }
+}
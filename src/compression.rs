@@ -0,0 +1,186 @@
+use std::ops::RangeInclusive;
+
+/// Coordinate compression of one axis: the values of a point set, sorted, each one
+/// becoming a cell whose index replaces the value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompressedAxis {
+    /// The values covered by each cell, in order. The ends are inclusive so that a cell
+    /// can hold `u64::MAX`.
+    cells: Vec<RangeInclusive<u64>>,
+}
+
+impl CompressedAxis {
+    /// One cell per distinct value, the values between them being dropped.
+    pub fn new(values: impl IntoIterator<Item = u64>) -> Self {
+        CompressedAxis {
+            cells: CompressedAxis::sorted(values)
+                .into_iter()
+                .map(|v| v..=v)
+                .collect(),
+        }
+    }
+
+    /// One cell per distinct value, plus a gap cell standing for the values between two
+    /// values that are not adjacent, so that the space between them is not lost.
+    pub fn with_gaps(values: impl IntoIterator<Item = u64>) -> Self {
+        let mut cells: Vec<RangeInclusive<u64>> = Vec::new();
+        for v in CompressedAxis::sorted(values) {
+            // the values are distinct, the previous one is below `v`
+            if let Some(last) = cells.last()
+                && *last.end() + 1 < v
+            {
+                cells.push(*last.end() + 1..=v - 1);
+            }
+            cells.push(v..=v);
+        }
+        CompressedAxis { cells }
+    }
+
+    fn sorted(values: impl IntoIterator<Item = u64>) -> Vec<u64> {
+        let mut values: Vec<_> = values.into_iter().collect();
+        values.sort_unstable();
+        values.dedup();
+        values
+    }
+
+    /// Number of cells.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Index of the cell holding the value, found by binary search.
+    pub fn compress(&self, value: u64) -> Option<usize> {
+        let i = self.cells.partition_point(|c| *c.end() < value);
+        self.cells.get(i).filter(|c| c.contains(&value)).map(|_| i)
+    }
+
+    /// First value of the cell.
+    pub fn decompress(&self, index: usize) -> Option<u64> {
+        self.cells.get(index).map(|c| *c.start())
+    }
+
+    /// Values covered by the cell.
+    pub fn cell(&self, index: usize) -> Option<RangeInclusive<u64>> {
+        self.cells.get(index).cloned()
+    }
+
+    /// Number of values covered by the cell, 1 unless it is a gap.
+    pub fn width(&self, index: usize) -> Option<u64> {
+        self.span(index, index)
+    }
+
+    /// Number of values covered by the cells from `from` to `to`, both included, `None`
+    /// if a cell does not exist or if the count does not fit in a `u64`.
+    pub fn span(&self, from: usize, to: usize) -> Option<u64> {
+        let (from, to) = (from.min(to), from.max(to));
+        (self.cells.get(to)?.end() - self.cells.get(from)?.start()).checked_add(1)
+    }
+}
+
+/// Coordinate compression of every axis of a set of points with `N` coordinates.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompressedSpace<const N: usize> {
+    axes: [CompressedAxis; N],
+}
+
+impl<const N: usize> CompressedSpace<N> {
+    /// Compresses each axis with the coordinates of the points, with gap cells or not.
+    pub fn new(points: &[[u64; N]], gaps: bool) -> Self {
+        let axes = std::array::from_fn(|i| {
+            let values = points.iter().map(|p| p[i]);
+            if gaps {
+                CompressedAxis::with_gaps(values)
+            } else {
+                CompressedAxis::new(values)
+            }
+        });
+        CompressedSpace { axes }
+    }
+
+    pub fn axis(&self, index: usize) -> &CompressedAxis {
+        &self.axes[index]
+    }
+
+    pub fn compress(&self, point: &[u64; N]) -> Option<[usize; N]> {
+        let mut res = [0; N];
+        for (i, axis) in self.axes.iter().enumerate() {
+            res[i] = axis.compress(point[i])?;
+        }
+        Some(res)
+    }
+
+    pub fn decompress(&self, point: &[usize; N]) -> Option<[u64; N]> {
+        let mut res = [0; N];
+        for (i, axis) in self.axes.iter().enumerate() {
+            res[i] = axis.decompress(point[i])?;
+        }
+        Some(res)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{CompressedAxis, CompressedSpace};
+
+    #[test]
+    fn axis() {
+        let axis = CompressedAxis::new([30, 10, 11, 30, 50]);
+        assert_eq!(axis.len(), 4);
+        assert_eq!(axis.compress(11), Some(1));
+        assert_eq!(axis.compress(50), Some(3));
+        assert_eq!(axis.compress(20), None);
+        assert_eq!(axis.decompress(2), Some(30));
+        assert_eq!(axis.decompress(4), None);
+        assert_eq!(axis.span(0, 3), Some(41));
+        assert_eq!(axis.span(0, 4), None);
+    }
+
+    #[test]
+    fn gaps() {
+        let axis = CompressedAxis::with_gaps([30, 10, 11, 50]);
+        // 10, 11, 12..30, 30, 31..50, 50
+        assert_eq!(axis.len(), 6);
+        assert_eq!(axis.compress(20), Some(2));
+        assert_eq!(axis.compress(30), Some(3));
+        assert_eq!(axis.compress(9), None);
+        assert_eq!(axis.compress(51), None);
+        assert_eq!(axis.cell(4), Some(31..=49));
+        assert_eq!(axis.width(2), Some(18));
+        assert_eq!(axis.width(3), Some(1));
+        assert_eq!(axis.width(6), None);
+        assert_eq!(
+            (0..axis.len()).map(|i| axis.width(i)).sum::<Option<u64>>(),
+            Some(41)
+        );
+    }
+
+    #[test]
+    fn limits() {
+        let axis = CompressedAxis::with_gaps([u64::MAX, 0, u64::MAX - 1]);
+        // 0, 1..MAX - 1, MAX - 1, MAX
+        assert_eq!(axis.len(), 4);
+        assert_eq!(axis.compress(u64::MAX), Some(3));
+        assert_eq!(axis.decompress(3), Some(u64::MAX));
+        assert_eq!(axis.width(1), Some(u64::MAX - 2));
+        assert_eq!(axis.span(1, 3), Some(u64::MAX));
+        assert_eq!(axis.span(0, 3), None);
+        assert_eq!(CompressedAxis::new([u64::MAX]).width(0), Some(1));
+    }
+
+    #[test]
+    fn space() {
+        let points = [[5, 100, 7], [1, 100, 3], [5, 2, 3]];
+        let space = CompressedSpace::new(&points, false);
+        assert_eq!(space.compress(&[5, 100, 7]), Some([1, 1, 1]));
+        assert_eq!(space.compress(&[5, 100, 4]), None);
+        assert_eq!(space.decompress(&[0, 0, 1]), Some([1, 2, 7]));
+        let space = CompressedSpace::new(&points, true);
+        assert_eq!(space.axis(0).len(), 3);
+        assert_eq!(space.compress(&[3, 50, 5]), Some([1, 1, 1]));
+    }
+}
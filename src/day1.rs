@@ -6,19 +6,22 @@ use nom::combinator::map;
 use nom::multi::separated_list0;
 use nom::sequence::pair;
 
+use crate::error::{Result, parse_all};
+use crate::solution::Solution;
+
 #[derive(Debug)]
-enum RotationDirection {
+pub enum RotationDirection {
     Left,
     Right,
 }
 
 #[derive(Debug)]
-struct Rotation {
-    direction: RotationDirection,
-    steps: u64,
+pub struct Rotation {
+    pub direction: RotationDirection,
+    pub steps: u64,
 }
 
-fn rotation(input: &str) -> IResult<&str, Rotation> {
+pub fn rotation(input: &str) -> IResult<&str, Rotation> {
     map(pair(direction, parse_u64), |(direction, steps)| Rotation {
         direction,
         steps,
@@ -26,7 +29,7 @@ fn rotation(input: &str) -> IResult<&str, Rotation> {
     .parse(input)
 }
 
-fn direction(input: &str) -> IResult<&str, RotationDirection> {
+pub fn direction(input: &str) -> IResult<&str, RotationDirection> {
     alt((
         map(char('L'), |_| RotationDirection::Left),
         map(char('R'), |_| RotationDirection::Right),
@@ -34,11 +37,11 @@ fn direction(input: &str) -> IResult<&str, RotationDirection> {
     .parse(input)
 }
 
-fn rotations(input: &str) -> IResult<&str, Vec<Rotation>> {
+pub fn rotations(input: &str) -> IResult<&str, Vec<Rotation>> {
     separated_list0(char('\n'), rotation).parse(input)
 }
 
-fn compute_code(rotations: &[Rotation]) -> u32 {
+pub fn compute_code(rotations: &[Rotation]) -> u32 {
     let mut position: i64 = 50;
     let mut code = 0;
 
@@ -60,7 +63,7 @@ fn compute_code(rotations: &[Rotation]) -> u32 {
     code
 }
 
-fn compute_code_2(rotations: &[Rotation]) -> u32 {
+pub fn compute_code_2(rotations: &[Rotation]) -> u32 {
     let mut position: i64 = 50;
     let mut code = 0;
 
@@ -91,35 +94,42 @@ fn compute_code_2(rotations: &[Rotation]) -> u32 {
     code
 }
 
-pub fn run(input: &str) {
-    let (_, rotations) = rotations(input).expect("Could not parse the input rotations");
-    println!("=> part1 : {}", compute_code(&rotations));
-    println!("=> part2 : {}", compute_code_2(&rotations));
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = Vec<Rotation>;
+    type Output1 = u32;
+    type Output2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_all(input, rotations)
+    }
+
+    fn part1(rotations: &Self::Input) -> Result<u32> {
+        Ok(compute_code(rotations))
+    }
+
+    fn part2(rotations: &Self::Input) -> Result<u32> {
+        Ok(compute_code_2(rotations))
+    }
 }
 
 #[cfg(test)]
 mod tests {
 
+    use crate::examples::{DAY1, DAY1_FULL_TURNS};
+
     use super::{compute_code, compute_code_2, rotations};
 
     #[test]
     fn test() {
-        let data = "L68
-L30
-R48
-L5
-R60
-L55
-L1
-L99
-R14
-L82";
+        let data = DAY1;
 
         let (_, rots) = rotations(data).expect("Could not parse the input rotations");
         assert_eq!(compute_code(&rots), 3);
         assert_eq!(compute_code_2(&rots), 6);
 
-        let data = "R1000\nL1000";
+        let data = DAY1_FULL_TURNS;
         let (_, rots) = rotations(data).expect("Could not parse the input rotations");
         assert_eq!(compute_code_2(&rots), 20);
     }
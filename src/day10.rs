@@ -1,5 +1,3 @@
-use std::{error::Error, fmt::Display};
-
 use cached::UnboundCache;
 use cached::proc_macro::cached;
 use nom::{
@@ -13,19 +11,23 @@ use nom::{
 use rayon::prelude::*;
 use z3::{Optimize, SatResult, ast::Int};
 
+use crate::error::{Error, Result, parse_all};
+use crate::solution::Solution;
+
 #[cached(
     ty = "UnboundCache<String, u64>",
     create = "{ UnboundCache::new() }",
-    convert = r#"{ format!("{:?}{:?}{:?}", init, target, buttons) }"#
+    convert = r#"{ format!("{:?}{:?}{:?}", init, target, buttons) }"#,
+    result = true
 )]
-fn search_shortest_lights(init: &Lights, target: &Lights, buttons: &[Button]) -> u64 {
-    let lights_to_toggle = init.diff(target).expect("Could not perform diff on lights");
+fn search_shortest_lights(init: &Lights, target: &Lights, buttons: &[Button]) -> Result<u64> {
+    let lights_to_toggle = init.diff(target)?;
     if lights_to_toggle.is_empty() {
-        return 0;
+        return Ok(0);
     }
 
     if buttons.is_empty() {
-        return u64::MAX;
+        return Ok(u64::MAX);
     }
 
     let possible_starts = buttons
@@ -44,19 +46,19 @@ fn search_shortest_lights(init: &Lights, target: &Lights, buttons: &[Button]) ->
         let mut current = (*init).clone();
         current.apply_button(possible_start);
 
-        let res = search_shortest_lights(&current, target, &remaining_buttons);
+        let res = search_shortest_lights(&current, target, &remaining_buttons)?;
         if res < min_path {
             min_path = res;
         }
     }
-    min_path.saturating_add(1)
+    Ok(min_path.saturating_add(1))
 }
 
 // This works but does not converge quickly enough
-fn search_shortest_joltages(init: &Joltage, target: &Joltage, buttons: &[Button]) -> u64 {
-    let counter_to_increase = init.diff(target).expect("Could not perform diff");
+fn search_shortest_joltages(init: &Joltage, target: &Joltage, buttons: &[Button]) -> Result<u64> {
+    let counter_to_increase = init.diff(target)?;
     if counter_to_increase.is_empty() {
-        return 0;
+        return Ok(0);
     }
 
     let possible_buttons: Vec<_> = buttons
@@ -71,12 +73,12 @@ fn search_shortest_joltages(init: &Joltage, target: &Joltage, buttons: &[Button]
         let mut current = (*init).clone();
         current.apply_button(&possible_button);
 
-        let res = search_shortest_joltages(&current, target, buttons);
+        let res = search_shortest_joltages(&current, target, buttons)?;
         if res < min_path {
             min_path = res;
         }
     }
-    min_path.saturating_add(1)
+    Ok(min_path.saturating_add(1))
 }
 
 // Z3 solutions
@@ -84,7 +86,7 @@ fn solver_part1(target: &Lights, buttons: &[Button]) -> u64 {
     todo!()
 }
 
-fn solver_part2(target: &Joltage, buttons: &[Button]) -> u64 {
+fn solver_part2(target: &Joltage, buttons: &[Button]) -> Result<u64> {
     // Create X vector = number of press on each button
     let x: Vec<_> = (0..buttons.len())
         .map(|i| Int::new_const(format!("x{i}")))
@@ -116,25 +118,32 @@ fn solver_part2(target: &Joltage, buttons: &[Button]) -> u64 {
     opt.minimize(&obj);
 
     // Check if a solution exists
-    let mut res = u64::MAX;
     match opt.check(&[]) {
         SatResult::Sat => {
-            let model = opt.get_model().unwrap();
-            let solution: Vec<u64> = x
+            let model = opt
+                .get_model()
+                .ok_or_else(|| Error::NoSolution("Z3 did not provide a model".to_string()))?;
+            let solution = x
                 .iter()
-                .map(|xi| model.eval(xi, true).unwrap().as_u64().unwrap())
-                .collect();
-            res = solution.iter().sum();
+                .map(|xi| model.eval(xi, true).and_then(|v| v.as_u64()))
+                .collect::<Option<Vec<u64>>>()
+                .ok_or_else(|| {
+                    Error::NoSolution("Z3 gave a press count that is not a u64".to_string())
+                })?;
             // println!("Minimal norm solution: {solution:?}");
-            // println!("Norm: {res}");
+            Ok(solution.iter().sum())
         }
-        SatResult::Unsat => println!("No solution exists."),
-        SatResult::Unknown => println!("Z3 could not determine satisfiability."),
+        SatResult::Unsat => Err(Error::NoSolution(format!(
+            "the joltages {:?} cannot be reached",
+            target.numbers
+        ))),
+        SatResult::Unknown => Err(Error::NoSolution(
+            "Z3 could not determine satisfiability".to_string(),
+        )),
     }
-    res
 }
 
-fn part1(machines: &[(Lights, Vec<Button>, Joltage)]) -> u64 {
+pub fn part1(machines: &[(Lights, Vec<Button>, Joltage)]) -> Result<u64> {
     machines
         .into_par_iter()
         .map(|(lights, buttons, _)| {
@@ -143,22 +152,36 @@ fn part1(machines: &[(Lights, Vec<Button>, Joltage)]) -> u64 {
         .sum()
 }
 
-fn part2(machines: &[(Lights, Vec<Button>, Joltage)]) -> u64 {
+pub fn part2(machines: &[(Lights, Vec<Button>, Joltage)]) -> Result<u64> {
     machines
         .into_par_iter()
         .map(|(_, buttons, joltages)| solver_part2(joltages, buttons))
         .sum()
 }
 
-pub fn run(input: &str) {
-    let (_, machines) = machines(input).expect("Could not parse input problems");
-    println!("=> part1 : {}", part1(&machines));
-    println!("=> part2 : {}", part2(&machines));
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = Vec<Machine>;
+    type Output1 = u64;
+    type Output2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_all(input, machines)
+    }
+
+    fn part1(machines: &Self::Input) -> Result<u64> {
+        part1(machines)
+    }
+
+    fn part2(machines: &Self::Input) -> Result<u64> {
+        part2(machines)
+    }
 }
 
 // === PARSERS ===
 
-fn lights(input: &str) -> IResult<&str, Lights> {
+pub fn lights(input: &str) -> IResult<&str, Lights> {
     let light = map(one_of(".#"), |c| match c {
         '.' => LightStatus::Off,
         '#' => LightStatus::On,
@@ -167,20 +190,20 @@ fn lights(input: &str) -> IResult<&str, Lights> {
     let (remainder, lights) = delimited(tag("["), many1(light), tag("]")).parse(input)?;
     Ok((remainder, Lights { status: lights }))
 }
-fn button(input: &str) -> IResult<&str, Button> {
+pub fn button(input: &str) -> IResult<&str, Button> {
     let (remainder, button) =
         delimited(tag("("), separated_list1(char(','), u64), tag(")")).parse(input)?;
     Ok((remainder, Button { numbers: button }))
 }
-fn buttons(input: &str) -> IResult<&str, Vec<Button>> {
+pub fn buttons(input: &str) -> IResult<&str, Vec<Button>> {
     separated_list1(char(' '), button).parse(input)
 }
-fn joltage(input: &str) -> IResult<&str, Joltage> {
+pub fn joltage(input: &str) -> IResult<&str, Joltage> {
     let (remainder, numbers) =
         delimited(tag("{"), separated_list1(char(','), u64), tag("}")).parse(input)?;
     Ok((remainder, Joltage { numbers }))
 }
-fn machine(input: &str) -> IResult<&str, Machine> {
+pub fn machine(input: &str) -> IResult<&str, Machine> {
     let (remainder, lights) = lights(input)?;
     let (remainder, _) = char(' ').parse(remainder)?;
     let (remainder, buttons) = buttons(remainder)?;
@@ -188,21 +211,21 @@ fn machine(input: &str) -> IResult<&str, Machine> {
     let (remainder, joltage) = joltage(remainder)?;
     Ok((remainder, (lights, buttons, joltage)))
 }
-fn machines(input: &str) -> IResult<&str, Vec<Machine>> {
+pub fn machines(input: &str) -> IResult<&str, Vec<Machine>> {
     separated_list1(char('\n'), machine).parse(input)
 }
 
 // === Data structures ===
 
-type Machine = (Lights, Vec<Button>, Joltage);
+pub type Machine = (Lights, Vec<Button>, Joltage);
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
-struct Lights {
-    status: Vec<LightStatus>,
+pub struct Lights {
+    pub status: Vec<LightStatus>,
 }
 
 impl Lights {
-    fn new(len: usize) -> Self {
+    pub fn new(len: usize) -> Self {
         Lights {
             status: vec![LightStatus::Off; len],
         }
@@ -214,9 +237,12 @@ impl Lights {
             }
         });
     }
-    fn diff(&self, other: &Self) -> Result<Vec<u64>, DiffError> {
+    fn diff(&self, other: &Self) -> Result<Vec<u64>> {
         if self.status.len() != other.status.len() {
-            Err(DiffError)
+            Err(Error::LengthMismatch {
+                expected: self.status.len(),
+                actual: other.status.len(),
+            })
         } else {
             Ok(self
                 .status
@@ -230,7 +256,7 @@ impl Lights {
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Hash)]
-enum LightStatus {
+pub enum LightStatus {
     On,
     Off,
 }
@@ -244,35 +270,29 @@ impl LightStatus {
     }
 }
 
-#[derive(Debug)]
-struct DiffError;
-impl Error for DiffError {}
-impl Display for DiffError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Could not perform diff of lights")
-    }
-}
-
 #[derive(Debug, PartialEq, Eq, Clone, Hash)]
-struct Button {
-    numbers: Vec<u64>,
+pub struct Button {
+    pub numbers: Vec<u64>,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
-struct Joltage {
-    numbers: Vec<u64>,
+pub struct Joltage {
+    pub numbers: Vec<u64>,
 }
 
 impl Joltage {
-    fn new(len: usize) -> Self {
+    pub fn new(len: usize) -> Self {
         Self {
             numbers: vec![0; len],
         }
     }
 
-    fn diff(&self, other: &Self) -> Result<Vec<u64>, DiffError> {
+    fn diff(&self, other: &Self) -> Result<Vec<u64>> {
         if self.numbers.len() != other.numbers.len() {
-            Err(DiffError)
+            Err(Error::LengthMismatch {
+                expected: self.numbers.len(),
+                actual: other.numbers.len(),
+            })
         } else {
             Ok(self
                 .numbers
@@ -297,19 +317,18 @@ impl Joltage {
 mod tests {
 
     use crate::day10::{button, joltage, lights, machine, machines};
+    use crate::examples::DAY10;
 
     use super::{part1, part2};
 
     #[test]
     fn example_data() {
-        let data = "[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}
-[...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}
-[.###.#] (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}";
+        let data = DAY10;
 
         let (_, machines) = machines(data).expect("Could not parse the input data");
         assert_eq!(machines.len(), 3);
-        assert_eq!(part1(&machines), 7);
-        assert_eq!(part2(&machines), 33);
+        assert_eq!(part1(&machines).expect("Could not solve part 1"), 7);
+        assert_eq!(part2(&machines).expect("Could not solve part 2"), 33);
     }
 
     #[test]
@@ -1,6 +1,3 @@
-use std::collections::HashMap;
-use std::collections::{HashSet, VecDeque};
-
 use nom::{
     IResult, Parser,
     bytes::complete::tag,
@@ -8,133 +5,59 @@ use nom::{
     multi::separated_list1,
 };
 
-fn search_output(start: &str, target: &str, devices: &HashMap<String, &Device>) -> u64 {
-    let mut res = 0;
-    if let Some(current) = devices.get(start) {
-        current.outputs.iter().for_each(|out| {
-            if out == target {
-                res += 1;
-            } else {
-                res += search_output(out, target, devices)
-            }
-        });
-    }
-    res
-}
+use crate::error::{Result, parse_all};
+use crate::graph::Graph;
+use crate::solution::Solution;
 
-type Graph = HashMap<String, Device>;
-
-fn iterative_dfs(start: &str, target: &str, devices: &Graph) -> Vec<Vec<String>> {
-    let mut reachable: HashMap<String, bool> = HashMap::new();
-
-    fn is_reachable(
-        node: &str,
-        target: &str,
-        devices: &HashMap<String, Device>,
-        reachable: &mut HashMap<String, bool>,
-    ) -> bool {
-        if node == target {
-            return true;
-        }
-
-        if let Some(&result) = reachable.get(node) {
-            return result;
-        }
-
-        // Simple DFS to check reachability
-        let mut visited = HashSet::new();
-        let mut stack = vec![node];
-
-        while let Some(n) = stack.pop() {
-            if visited.contains(n) {
-                continue;
-            }
-            visited.insert(n);
-
-            if n == target {
-                reachable.insert(node.to_string(), true);
-                return true;
-            }
-
-            if let Some(device) = devices.get(n) {
-                for neighbor in &device.outputs {
-                    stack.push(neighbor.as_str());
-                }
-            }
-        }
-
-        reachable.insert(node.to_string(), false);
-        false
-    }
+/// Graph of the devices, linked to their outputs.
+pub fn graph(devices: &[Device]) -> Graph {
+    let mut graph = Graph::new();
+    devices.iter().for_each(|d| {
+        graph.node(&d.name);
+        d.outputs
+            .iter()
+            .for_each(|out| graph.add_edge(&d.name, out));
+    });
+    graph
+}
 
-    let mut all_paths = Vec::new();
-    let mut stack: VecDeque<(String, Vec<String>)> = VecDeque::new();
-
-    stack.push_back((start.to_string(), vec![start.to_string()]));
-
-    while let Some((node, path)) = stack.pop_back() {
-        if node == target {
-            all_paths.push(path);
-            continue; // Don't explore further from target
-        }
-
-        if let Some(neighbors) = devices.get(&node) {
-            for neighbor in neighbors.outputs.iter().rev() {
-                // Avoid cycles by checking if neighbor is already in path
-                if path.contains(neighbor) {
-                    continue;
-                }
-
-                if !is_reachable(neighbor, target, devices, &mut reachable) {
-                    continue;
-                }
-
-                let mut new_path = path.clone();
-                new_path.push(neighbor.clone());
-                stack.push_back((neighbor.to_owned(), new_path));
-            }
-        }
-    }
+pub fn part1(graph: &Graph) -> Result<u64> {
+    graph.count_paths("you", "out")
+}
 
-    all_paths
+pub fn part2(graph: &Graph) -> Result<u64> {
+    // the graph has no cycle, so at most one of the orders has paths
+    let through = |first, second| -> Result<u64> {
+        Ok(graph.count_paths("svr", first)?
+            * graph.count_paths(first, second)?
+            * graph.count_paths(second, "out")?)
+    };
+    Ok(through("fft", "dac")? + through("dac", "fft")?)
 }
 
-fn part1(devices: &[Device]) -> u64 {
-    let mut devices_map = HashMap::new();
-    devices.iter().for_each(|d| {
-        devices_map.insert(d.name.clone(), d);
-    });
+pub struct Day11;
 
-    search_output("you", "out", &devices_map)
-}
+impl Solution for Day11 {
+    type Input = Graph;
+    type Output1 = u64;
+    type Output2 = u64;
 
-fn part2(devices: &[Device]) -> u64 {
-    let mut devices_map = HashMap::new();
-    devices.iter().for_each(|d| {
-        devices_map.insert(d.name.clone(), (*d).clone());
-    });
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(graph(&parse_all(input, devices)?))
+    }
 
-    // I noticed that there is no path from dac to fft
-    // so I focused on searching paths going first to
-    // fft and then to dac
-    println!("searching paths from dac");
-    let mut res = iterative_dfs("dac", "out", &devices_map).len();
-    println!("searching paths from fft to dac");
-    res *= iterative_dfs("fft", "dac", &devices_map).len();
-    println!("searching paths from svr to fft");
-    res *= iterative_dfs("svr", "fft", &devices_map).len();
-    res as u64
-}
+    fn part1(graph: &Self::Input) -> Result<u64> {
+        part1(graph)
+    }
 
-pub fn run(input: &str) {
-    let (_, devices) = devices(input).expect("Could not parse input problems");
-    println!("=> part1 : {}", part1(&devices));
-    println!("=> part2 : {}", part2(&devices));
+    fn part2(graph: &Self::Input) -> Result<u64> {
+        part2(graph)
+    }
 }
 
 // === PARSERS ===
 
-fn device(input: &str) -> IResult<&str, Device> {
+pub fn device(input: &str) -> IResult<&str, Device> {
     let (remainder, (name, _, outputs)) =
         (alpha1, tag(": "), separated_list1(char(' '), alpha1)).parse(input)?;
     Ok((
@@ -145,62 +68,42 @@ fn device(input: &str) -> IResult<&str, Device> {
         },
     ))
 }
-fn devices(input: &str) -> IResult<&str, Vec<Device>> {
+pub fn devices(input: &str) -> IResult<&str, Vec<Device>> {
     separated_list1(char('\n'), device).parse(input)
 }
 
 // === Data structures ===
 
 #[derive(Debug, Clone)]
-struct Device {
-    name: String,
-    outputs: Vec<String>,
+pub struct Device {
+    pub name: String,
+    pub outputs: Vec<String>,
 }
 
 #[cfg(test)]
 mod tests {
 
-    use crate::day11::{Device, devices};
+    use crate::day11::{Device, devices, graph};
+    use crate::examples::{DAY11_SVR, DAY11_YOU};
 
     use super::{part1, part2};
 
     #[test]
     fn example_data() {
-        let data = "aaa: you hhh
-you: bbb ccc
-bbb: ddd eee
-ccc: ddd eee fff
-ddd: ggg
-eee: out
-fff: out
-ggg: out
-hhh: ccc fff iii
-iii: out";
+        let data = DAY11_YOU;
 
         let (_, dvs) = devices(data).expect("Could not parse the input data");
         assert_eq!(dvs.len(), 10);
-        assert_eq!(part1(&dvs), 5);
-
-        let data = "svr: aaa bbb
-aaa: fft
-fft: ccc
-bbb: tty
-tty: ccc
-ccc: ddd eee
-ddd: hub
-hub: fff
-eee: dac
-dac: fff
-fff: ggg hhh
-ggg: out
-hhh: out";
+        assert_eq!(part1(&graph(&dvs)).expect("Could not solve part 1"), 5);
+
+        let data = DAY11_SVR;
         let (_, mut dvs) = devices(data).expect("Could not parse the input data");
         assert_eq!(dvs.len(), 13);
         dvs.push(Device {
             name: "out".to_string(),
             outputs: Vec::new(),
         });
-        assert_eq!(part2(&dvs), 2);
+        assert_eq!(part2(&graph(&dvs)).expect("Could not solve part 2"), 2);
     }
 
     #[test]
@@ -5,9 +5,13 @@ use nom::{
     multi::{many0, many1, separated_list1},
 };
 
-fn part1(shapes: &[Shape], trees: &[Tree]) -> u64 {
+use crate::error::{Error, Result, parse_all};
+use crate::grid::Grid;
+use crate::solution::Solution;
+
+pub fn part1(shapes: &[Shape], trees: &[Tree]) -> u64 {
     // Count the shapes that fits below the tree
-    let trees_that_fits: Vec<_> = trees
+    trees
         .iter()
         .filter(|t| {
             let area = t.size.0 * t.size.1;
@@ -15,55 +19,65 @@ fn part1(shapes: &[Shape], trees: &[Tree]) -> u64 {
                 .gifts
                 .iter()
                 .enumerate()
-                .map(|(j, g)| {
-                    shapes[j]
-                        .chars
-                        .iter()
-                        .flatten()
-                        .filter(|&&c| c == '#')
-                        .count() as u64
-                        * *g
-                })
+                .map(|(j, g)| shapes[j].chars.iter().filter(|&&c| c == '#').count() as u64 * *g)
                 .sum();
             req_area <= area
         })
-        .collect();
-    println!(
-        "Remaining trees to consider : {} / {}",
-        trees_that_fits.len(),
-        trees.len()
-    );
-    trees_that_fits.len() as u64
+        .count() as u64
 }
 
-fn part2() -> u64 {
+pub fn part2() -> u64 {
     0
 }
 
-pub fn run(input: &str) {
-    let (remainder, shapes) = shapes(input).expect("Could not parse the shapes");
-    let (remainder, trees) = trees(remainder).expect("Could not parse the trees");
-    println!("=> part1 : {}", part1(&shapes, &trees));
-    println!("=> part2 : {}", part2());
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = (Vec<Shape>, Vec<Tree>);
+    type Output1 = u64;
+    type Output2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let (shapes, trees) = parse_all(input, (shapes, trees))?;
+        // every gift count refers to a shape by its position
+        if let Some(tree) = trees.iter().find(|t| t.gifts.len() > shapes.len()) {
+            return Err(Error::InvalidInput(format!(
+                "a {}x{} tree lists {} gifts for {} shapes",
+                tree.size.0,
+                tree.size.1,
+                tree.gifts.len(),
+                shapes.len()
+            )));
+        }
+        Ok((shapes, trees))
+    }
+
+    fn part1((shapes, trees): &Self::Input) -> Result<u64> {
+        Ok(part1(shapes, trees))
+    }
+
+    fn part2(_: &Self::Input) -> Result<u64> {
+        Ok(part2())
+    }
 }
 
 // === PARSERS ===
 
-fn shape(input: &str) -> IResult<&str, Shape> {
+pub fn shape(input: &str) -> IResult<&str, Shape> {
     (
         usize,
         tag(":\n"),
-        separated_list1(char('\n'), many1(one_of(".#"))),
+        separated_list1(char('\n'), many1(one_of(".#"))).map_opt(|rows| Grid::from_rows(rows).ok()),
     )
         .map(|(index, _, chars)| Shape { index, chars })
         .parse(input)
 }
 
-fn shapes(input: &str) -> IResult<&str, Vec<Shape>> {
+pub fn shapes(input: &str) -> IResult<&str, Vec<Shape>> {
     separated_list1(tag("\n\n"), shape).parse(input)
 }
 
-fn tree(input: &str) -> IResult<&str, Tree> {
+pub fn tree(input: &str) -> IResult<&str, Tree> {
     (
         many0(char('\n')),
         u64,
@@ -79,67 +93,34 @@ fn tree(input: &str) -> IResult<&str, Tree> {
         .parse(input)
 }
 
-fn trees(input: &str) -> IResult<&str, Vec<Tree>> {
+pub fn trees(input: &str) -> IResult<&str, Vec<Tree>> {
     separated_list1(char('\n'), tree).parse(input)
 }
 
 // === Data structures ===
 
 #[derive(Debug, Clone)]
-struct Shape {
-    index: usize,
-    chars: Vec<Vec<char>>,
+pub struct Shape {
+    pub index: usize,
+    pub chars: Grid<char>,
 }
 
-struct Tree {
-    size: (u64, u64),
-    gifts: Vec<u64>,
+pub struct Tree {
+    pub size: (u64, u64),
+    pub gifts: Vec<u64>,
 }
 
 #[cfg(test)]
 mod tests {
 
     use crate::day12::{shapes, trees};
+    use crate::examples::DAY12;
 
     use super::{part1, part2};
 
-    const DATA: &str = "0:
-###
-##.
-##.
-
-1:
-###
-##.
-.##
-
-2:
-.##
-###
-##.
-
-3:
-##.
-###
-##.
-
-4:
-###
-#..
-###
-
-5:
-###
-.#.
-###
-
-4x4: 0 0 0 0 2 0
-12x5: 1 0 1 0 2 2
-12x5: 1 0 1 0 3 2";
-
     #[test]
     fn example_data() {
-        let (remainder, shapes) = shapes(DATA).expect("Could not parse the shapes");
+        let (remainder, shapes) = shapes(DAY12).expect("Could not parse the shapes");
         let (remainder, trees) = trees(remainder).expect("Could not parse the trees");
 
         assert_eq!(part1(&shapes, &trees), 2);
@@ -148,9 +129,10 @@ mod tests {
 
     #[test]
     fn parsers() {
-        let (remainder, shapes) = shapes(DATA).expect("Could not parse the shapes");
+        let (remainder, shapes) = shapes(DAY12).expect("Could not parse the shapes");
         let (remainder, trees) = trees(remainder).expect("Could not parse the trees");
         assert_eq!(shapes.len(), 6);
+        assert_eq!(shapes[0].chars.to_string(), "###\n##.\n##.");
         assert_eq!(trees.len(), 3)
     }
 }
@@ -2,15 +2,19 @@ use nom::IResult;
 use nom::Parser;
 use nom::character::complete::{char, u64 as parse_u64};
 use nom::multi::separated_list0;
+use nom::sequence::separated_pair;
+
+use crate::error::{Result, parse_all};
+use crate::solution::Solution;
 
 #[derive(Debug)]
-struct IdRange {
-    first: u64,
-    last: u64,
+pub struct IdRange {
+    pub first: u64,
+    pub last: u64,
 }
 
 impl IdRange {
-    fn get_invalid_ids(&self) -> Vec<u64> {
+    pub fn get_invalid_ids(&self) -> Vec<u64> {
         let mut res = Vec::new();
         for id in self.first..self.last + 1 {
             if !is_valid(&id) {
@@ -20,7 +24,7 @@ impl IdRange {
         res
     }
 
-    fn get_invalid_ids2(&self) -> Vec<u64> {
+    pub fn get_invalid_ids2(&self) -> Vec<u64> {
         let mut res = Vec::new();
         for id in self.first..self.last + 1 {
             if !is_valid2(&id) {
@@ -31,7 +35,7 @@ impl IdRange {
     }
 }
 
-fn is_valid(id: &u64) -> bool {
+pub fn is_valid(id: &u64) -> bool {
     let id_str = format!("{}", id);
     if id_str.len() % 2 != 0 {
         return true;
@@ -46,7 +50,7 @@ fn is_valid(id: &u64) -> bool {
     false
 }
 
-fn is_valid2(id: &u64) -> bool {
+pub fn is_valid2(id: &u64) -> bool {
     let id_str = format!("{}", id);
     let mut pattern_size = id_str.len() - 1;
     while pattern_size >= 1 {
@@ -69,22 +73,17 @@ fn is_valid2(id: &u64) -> bool {
     true
 }
 
-fn range(input: &str) -> IResult<&str, IdRange> {
-    let (remainder, ids) = separated_list0(char('-'), parse_u64).parse(input)?;
-    Ok((
-        remainder,
-        IdRange {
-            first: *ids.first().expect("did not find the first ID"),
-            last: *ids.get(1).expect("did not find the last ID"),
-        },
-    ))
+pub fn range(input: &str) -> IResult<&str, IdRange> {
+    let (remainder, (first, last)) =
+        separated_pair(parse_u64, char('-'), parse_u64).parse(input)?;
+    Ok((remainder, IdRange { first, last }))
 }
 
-fn ranges(input: &str) -> IResult<&str, Vec<IdRange>> {
+pub fn ranges(input: &str) -> IResult<&str, Vec<IdRange>> {
     separated_list0(char(','), range).parse(input)
 }
 
-fn part1(ranges_ids: &Vec<IdRange>) -> u64 {
+pub fn part1(ranges_ids: &Vec<IdRange>) -> u64 {
     let mut res = 0;
     for range in ranges_ids {
         res += range.get_invalid_ids().iter().sum::<u64>();
@@ -92,7 +91,7 @@ fn part1(ranges_ids: &Vec<IdRange>) -> u64 {
     res
 }
 
-fn part2(ranges_ids: &Vec<IdRange>) -> u64 {
+pub fn part2(ranges_ids: &Vec<IdRange>) -> u64 {
     let mut res = 0;
     for range in ranges_ids {
         res += range.get_invalid_ids2().iter().sum::<u64>();
@@ -100,21 +99,36 @@ fn part2(ranges_ids: &Vec<IdRange>) -> u64 {
     res
 }
 
-pub fn run(input: &str) {
-    let (_, r_ids) = ranges(input).expect("Could not parse the input rotations");
-    println!("=> part1 : {}", part1(&r_ids));
-    println!("=> part2 : {}", part2(&r_ids));
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Vec<IdRange>;
+    type Output1 = u64;
+    type Output2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_all(input, ranges)
+    }
+
+    fn part1(r_ids: &Self::Input) -> Result<u64> {
+        Ok(part1(r_ids))
+    }
+
+    fn part2(r_ids: &Self::Input) -> Result<u64> {
+        Ok(part2(r_ids))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::day2::{part1, part2};
+    use crate::examples::DAY2;
 
     use super::ranges;
 
     #[test]
     fn test() {
-        let data = "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,1698522-1698528,446443-446449,38593856-38593862,565653-565659,824824821-824824827,2121212118-2121212124";
+        let data = DAY2;
 
         let (_, ranges_ids) = ranges(data).expect("Could not parse the input ranges");
 
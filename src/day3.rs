@@ -5,18 +5,21 @@ use nom::combinator::map;
 use nom::multi::many1;
 use nom::multi::separated_list1;
 
-fn bank(input: &str) -> IResult<&str, Vec<u8>> {
+use crate::error::{Error, Result, parse_all};
+use crate::solution::Solution;
+
+pub fn bank(input: &str) -> IResult<&str, Vec<u8>> {
     many1(map(one_of("0123456789"), |c| {
         c.to_digit(10).expect("Could not parse u8 from char") as u8
     }))
     .parse(input)
 }
 
-fn banks(input: &str) -> IResult<&str, Vec<Vec<u8>>> {
+pub fn banks(input: &str) -> IResult<&str, Vec<Vec<u8>>> {
     separated_list1(char('\n'), bank).parse(input)
 }
 
-fn part1(banks: &Vec<Vec<u8>>) -> u64 {
+pub fn part1(banks: &Vec<Vec<u8>>) -> u64 {
     let mut res: u64 = 0;
     for bank in banks {
         // search for the max first digit
@@ -40,7 +43,9 @@ fn part1(banks: &Vec<Vec<u8>>) -> u64 {
     res
 }
 
-fn part2(banks: &Vec<Vec<u8>>) -> u64 {
+// the window bounds move together with the digit being picked
+#[allow(clippy::explicit_counter_loop)]
+pub fn part2(banks: &Vec<Vec<u8>>) -> u64 {
     let mut res: u64 = 0;
     for bank in banks {
         let mut last_max_index = 0;
@@ -63,22 +68,48 @@ fn part2(banks: &Vec<Vec<u8>>) -> u64 {
     res
 }
 
-pub fn run(input: &str) {
-    let (_, r_ids) = banks(input).expect("Could not parse the input banks");
-    println!("=> part1 : {}", part1(&r_ids));
-    println!("=> part2 : {}", part2(&r_ids));
+/// Both parts pick a fixed number of digits in every bank.
+fn check_banks_length(banks: &[Vec<u8>], digits: usize) -> Result<()> {
+    match banks.iter().position(|b| b.len() < digits) {
+        Some(i) => Err(Error::InvalidInput(format!(
+            "bank {} has less than {digits} batteries",
+            i + 1
+        ))),
+        None => Ok(()),
+    }
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = Vec<Vec<u8>>;
+    type Output1 = u64;
+    type Output2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_all(input, banks)
+    }
+
+    fn part1(banks: &Self::Input) -> Result<u64> {
+        check_banks_length(banks, 2)?;
+        Ok(part1(banks))
+    }
+
+    fn part2(banks: &Self::Input) -> Result<u64> {
+        check_banks_length(banks, 12)?;
+        Ok(part2(banks))
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::examples::DAY3;
+
     use super::{banks, part1, part2};
 
     #[test]
     fn test() {
-        let data = "987654321111111
-811111111111119
-234234234234278
-818181911112111";
+        let data = DAY3;
 
         let (_, banks) = banks(data).expect("Could not parse the input banks");
 
@@ -1,103 +1,55 @@
-use nom::IResult;
-use nom::Parser;
-use nom::branch::alt;
-use nom::character::complete::char;
-use nom::combinator::map;
-use nom::multi::many1;
-use nom::multi::separated_list1;
-
-#[derive(Debug)]
-struct Cell {
-    occupied: bool,
+use std::fmt::Display;
+
+use crate::error::Result;
+use crate::grid::{Grid, Position};
+use crate::solution::Solution;
+
+#[derive(Debug, Clone)]
+pub struct Cell {
+    pub occupied: bool,
 }
 
-fn cell(input: &str) -> IResult<&str, Cell> {
-    alt((
-        map(char('.'), |_| Cell { occupied: false }),
-        map(char('@'), |_| Cell { occupied: true }),
-    ))
-    .parse(input)
+impl Cell {
+    pub fn from_char(c: char) -> Option<Cell> {
+        match c {
+            '.' => Some(Cell { occupied: false }),
+            '@' => Some(Cell { occupied: true }),
+            _ => None,
+        }
+    }
 }
 
-fn cell_row(input: &str) -> IResult<&str, Vec<Cell>> {
-    many1(cell).parse(input)
+impl Display for Cell {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", if self.occupied { '@' } else { '.' })
+    }
 }
 
-fn grid(input: &str) -> IResult<&str, Vec<Vec<Cell>>> {
-    separated_list1(char('\n'), cell_row).parse(input)
+pub fn grid(input: &str) -> Result<Grid<Cell>> {
+    Grid::parse(input, Cell::from_char)
 }
 
-fn part1(cells: &[Vec<Cell>]) -> u64 {
-    let mut res = 0;
-    for i in 0..cells.len() {
-        for j in 0..cells[0].len() {
-            if !cells[i][j].occupied {
-                // no need to check
-                continue;
-            }
-
-            let mut count = 0;
-            let positions_to_check = vec![
-                (i.wrapping_sub(1), j.wrapping_sub(1)),
-                (i.wrapping_sub(1), j),
-                (i.wrapping_sub(1), j.wrapping_add(1)),
-                (i, j.wrapping_sub(1)),
-                (i, j.wrapping_add(1)),
-                (i.wrapping_add(1), j.wrapping_sub(1)),
-                (i.wrapping_add(1), j),
-                (i.wrapping_add(1), j.wrapping_add(1)),
-            ];
-            for (x, y) in positions_to_check {
-                if let Some(c) = cells.get(x)
-                    && let Some(cc) = c.get(y)
-                    && cc.occupied
-                {
-                    count += 1;
-                }
-            }
-            if count < 4 {
-                res += 1;
-            }
-        }
-    }
-    res
+pub fn part1(cells: &Grid<Cell>) -> u64 {
+    cells_to_be_cleared(cells).len() as u64
 }
-fn cells_to_be_cleared(cells: &[Vec<Cell>]) -> Vec<(usize, usize)> {
-    let mut res = Vec::new();
-    for i in 0..cells.len() {
-        for j in 0..cells[0].len() {
-            if !cells[i][j].occupied {
-                // no need to check
-                continue;
-            }
-
-            let mut count = 0;
-            let positions_to_check = vec![
-                (i.wrapping_sub(1), j.wrapping_sub(1)),
-                (i.wrapping_sub(1), j),
-                (i.wrapping_sub(1), j.wrapping_add(1)),
-                (i, j.wrapping_sub(1)),
-                (i, j.wrapping_add(1)),
-                (i.wrapping_add(1), j.wrapping_sub(1)),
-                (i.wrapping_add(1), j),
-                (i.wrapping_add(1), j.wrapping_add(1)),
-            ];
-            for (x, y) in positions_to_check {
-                if let Some(c) = cells.get(x)
-                    && let Some(cc) = c.get(y)
-                    && cc.occupied
-                {
-                    count += 1;
-                }
-            }
-            if count < 4 {
-                res.push((i, j));
-            }
-        }
-    }
-    res
+
+fn cells_to_be_cleared(cells: &Grid<Cell>) -> Vec<Position> {
+    cells
+        .enumerate()
+        // no need to check the empty cells
+        .filter(|(_, cell)| cell.occupied)
+        .filter(|(position, _)| {
+            cells
+                .neighbors8(*position)
+                .filter(|p| cells[*p].occupied)
+                .count()
+                < 4
+        })
+        .map(|(position, _)| position)
+        .collect()
 }
-fn part2(cells: &mut [Vec<Cell>]) -> usize {
+
+pub fn part2(cells: &mut Grid<Cell>) -> usize {
     let mut res = 0;
 
     loop {
@@ -106,39 +58,45 @@ fn part2(cells: &mut [Vec<Cell>]) -> usize {
             break;
         }
         res += accessible_cells.len();
-        for (x, y) in accessible_cells {
-            let c = cells.get_mut(x).expect("error").get_mut(y).expect("error");
-            c.occupied = false;
+        for position in accessible_cells {
+            cells[position].occupied = false;
         }
     }
 
     res
 }
 
-pub fn run(input: &str) {
-    let (_, mut cells) = grid(input).expect("Could not parse the input banks");
-    println!("=> part1 : {}", part1(&cells));
-    println!("=> part2 : {}", part2(&mut cells));
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input = Grid<Cell>;
+    type Output1 = u64;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        grid(input)
+    }
+
+    fn part1(cells: &Self::Input) -> Result<u64> {
+        Ok(part1(cells))
+    }
+
+    fn part2(cells: &Self::Input) -> Result<usize> {
+        Ok(part2(&mut cells.clone()))
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::examples::DAY4;
+
     use super::{grid, part1, part2};
 
     #[test]
     fn test() {
-        let data = "..@@.@@@@.
-@@@.@.@.@@
-@@@@@.@.@@
-@.@@@@..@.
-@@.@@@@.@@
-.@@@@@@@.@
-.@.@.@.@@@
-@.@@@.@@@@
-.@@@@@@@@.
-@.@.@@@.@.";
-
-        let (_, mut cells) = grid(data).expect("Could not parse the input banks");
+        let data = DAY4;
+
+        let mut cells = grid(data).expect("Could not parse the paper-roll grid");
 
         assert_eq!(part1(&cells), 13);
         assert_eq!(part2(&mut cells), 43);
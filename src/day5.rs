@@ -1,30 +1,36 @@
+use std::ops::Range;
+
 use nom::IResult;
 use nom::Parser;
 use nom::character::complete::{char, u64};
 use nom::multi::many1;
 use nom::multi::separated_list1;
+use nom::sequence::separated_pair;
+
+use crate::error::{Error, Result, parse_all};
+use crate::interval::IntervalSet;
+use crate::solution::Solution;
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct FoodRange {
+    pub start: u64,
+    pub end: u64,
+}
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
-struct FoodRange {
-    start: u64,
-    end: u64,
+impl FoodRange {
+    /// The ids of the range, the end being inclusive. They are widened so that a range
+    /// ending at `u64::MAX` has an exclusive end too.
+    pub fn ids(&self) -> Range<u128> {
+        self.start as u128..self.end as u128 + 1
+    }
 }
 
-fn range(input: &str) -> IResult<&str, FoodRange> {
-    let (remainder, range) = separated_list1(char('-'), u64).parse(input)?;
-    Ok((
-        remainder,
-        FoodRange {
-            start: *range.first()
-                .expect("Did not find the first element of the range"),
-            end: *range
-                .get(1)
-                .expect("Did not find the second element of the range"),
-        },
-    ))
+pub fn range(input: &str) -> IResult<&str, FoodRange> {
+    let (remainder, (start, end)) = separated_pair(u64, char('-'), u64).parse(input)?;
+    Ok((remainder, FoodRange { start, end }))
 }
 
-fn food_database(input: &str) -> IResult<&str, (Vec<FoodRange>, Vec<u64>)> {
+pub fn food_database(input: &str) -> IResult<&str, (Vec<FoodRange>, Vec<u64>)> {
     let (remainder, ranges) = separated_list1(char('\n'), range).parse(input)?;
     // remove all the new lines char
     let (remainder, _) = many1(char('\n')).parse(remainder)?;
@@ -32,81 +38,77 @@ fn food_database(input: &str) -> IResult<&str, (Vec<FoodRange>, Vec<u64>)> {
     Ok((remainder, (ranges, food_ids)))
 }
 
-fn part1(ranges: &[FoodRange], food_ids: &[u64]) -> u64 {
-    let mut cpt = 0;
-    for id in food_ids {
-        for range in ranges {
-            if *id >= range.start && *id <= range.end {
-                cpt += 1;
-                break;
-            }
-        }
-    }
-    cpt
+pub fn part1(fresh: &IntervalSet<u128>, food_ids: &[u64]) -> u64 {
+    food_ids
+        .iter()
+        .filter(|id| fresh.contains(**id as u128))
+        .count() as u64
 }
-fn part2(ranges: &mut [FoodRange]) -> u64 {
-    ranges.sort();
-    let mut res = 0;
-    let mut iter = ranges.iter_mut().peekable();
-    while let Some(range) = iter.next() {
-        // dbg!(&range);
-        if let Some(next) = iter.peek_mut() {
-            // next is inside the current range
-            // we change the end of the next so that it checks that no other range is inside of the
-            // current
-            if next.end <= range.end {
-                next.end = range.end;
-                res += next.start - range.start;
-
-            // next starts before the end of the current range
-            } else if next.start <= range.end {
-                res += next.start - range.start;
-
-            // nominal case
-            } else {
-                res += range.end - range.start + 1;
-            }
-        } else {
-            // last range
-            res += range.end - range.start + 1;
-        }
-        // dbg!(res);
-    }
-    res
+
+pub fn part2(fresh: &IntervalSet<u128>) -> Result<u64> {
+    let total = fresh.total_len();
+    u64::try_from(total)
+        .map_err(|_| Error::InvalidInput(format!("{total} fresh ids do not fit in 64 bits")))
 }
 
-pub fn run(input: &str) {
-    let (_, (mut ranges, food_ids)) =
-        food_database(input).expect("Could not parse the input banks");
-    println!("=> part1 : {}", part1(&ranges, &food_ids));
-    println!("=> part2 : {}", part2(&mut ranges));
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = (IntervalSet<u128>, Vec<u64>);
+    type Output1 = u64;
+    type Output2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let (ranges, food_ids) = parse_all(input, food_database)?;
+        Ok((ranges.iter().map(FoodRange::ids).collect(), food_ids))
+    }
+
+    fn part1((fresh, food_ids): &Self::Input) -> Result<u64> {
+        Ok(part1(fresh, food_ids))
+    }
+
+    fn part2((fresh, _): &Self::Input) -> Result<u64> {
+        part2(fresh)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{food_database, part1, part2};
+    use crate::examples::DAY5;
+
+    use super::{FoodRange, food_database, part1, part2};
 
     #[test]
     fn test() {
-        let data = "3-5
-10-14
-16-20
-12-18
-9-21
-
-1
-5
-8
-11
-17
-32";
-
-        let (_, (mut ranges, food_ids)) =
-            food_database(data).expect("Could not parse the input banks");
-        // dbg!(&ranges);
-        // dbg!(&food_ids);
-
-        assert_eq!(part1(&ranges, &food_ids), 3);
-        assert_eq!(part2(&mut ranges), 16);
+        let data = DAY5;
+
+        let (_, (ranges, food_ids)) =
+            food_database(data).expect("Could not parse the food-ID database");
+        let fresh = ranges.iter().map(FoodRange::ids).collect();
+
+        assert_eq!(part1(&fresh, &food_ids), 3);
+        assert_eq!(part2(&fresh).expect("Could not count the fresh ids"), 16);
+    }
+
+    #[test]
+    fn largest_ids() {
+        let data = "5-18446744073709551615\n\n18446744073709551615\n4";
+        let (_, (ranges, food_ids)) =
+            food_database(data).expect("Could not parse the food-ID database");
+        let fresh = ranges.iter().map(FoodRange::ids).collect();
+        assert_eq!(part1(&fresh, &food_ids), 1);
+        assert_eq!(
+            part2(&fresh).expect("Could not count the fresh ids"),
+            u64::MAX - 4
+        );
+
+        let fresh = [FoodRange {
+            start: 0,
+            end: u64::MAX,
+        }]
+        .iter()
+        .map(FoodRange::ids)
+        .collect();
+        assert!(part2(&fresh).is_err());
     }
 }
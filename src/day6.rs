@@ -2,54 +2,69 @@ use std::collections::VecDeque;
 
 use nom::IResult;
 use nom::Parser;
-use nom::character::complete::one_of;
-use nom::character::complete::{multispace1, space1, u64};
+use nom::branch::alt;
+use nom::character::complete::{char, multispace1, space1, u64};
 use nom::combinator::map;
+use nom::error::ErrorKind;
 use nom::multi::separated_list1;
+use nom::sequence::terminated;
+
+use crate::error::{Error, Result, parse_all};
+use crate::input::Layout;
+use crate::solution::Solution;
 
 #[derive(Debug, Clone, Copy)]
-enum Operation {
+pub enum Operation {
     Add,
     Multiply,
 }
 
 #[derive(Debug)]
-struct Problem {
-    numbers: Vec<u64>,
-    operation: Operation,
+pub struct Problem {
+    pub numbers: Vec<u64>,
+    pub operation: Operation,
+}
+
+fn failure(input: &str, kind: ErrorKind) -> nom::Err<nom::error::Error<&str>> {
+    nom::Err::Failure(nom::error::Error::new(input, kind))
+}
+
+fn operation(input: &str) -> IResult<&str, Operation> {
+    alt((
+        map(char('+'), |_| Operation::Add),
+        map(char('*'), |_| Operation::Multiply),
+    ))
+    .parse(input)
 }
 
-fn problems(input: &str) -> IResult<&str, Vec<Problem>> {
+pub fn problems(input: &str) -> IResult<&str, Vec<Problem>> {
     let mut number_lines = Vec::new();
     let mut remainder = input;
-    while let Ok((new_remainder, numbers)) = separated_list1(space1::<_, ()>, u64).parse(remainder)
+    while let Ok((new_remainder, numbers)) =
+        terminated(separated_list1(space1::<_, ()>, u64), multispace1).parse(remainder)
     {
+        number_lines.push((remainder, numbers));
         remainder = new_remainder;
-        let (new_remainder, _) = multispace1::<_, ()>.parse(remainder).expect("");
-        remainder = new_remainder;
-        number_lines.push(numbers);
     }
 
-    let (new_remainder, signs) = separated_list1(
-        space1,
-        map(one_of("+*"), |c| match c {
-            '+' => Some(Operation::Add),
-            '*' => Some(Operation::Multiply),
-            _ => None,
-        }),
-    )
-    .parse(remainder)?;
+    let (new_remainder, signs) = separated_list1(space1, operation).parse(remainder)?;
+
+    // each line holds one number of every problem
+    if let Some((line, _)) = number_lines
+        .iter()
+        .find(|(_, numbers)| numbers.len() != signs.len())
+    {
+        return Err(failure(line, ErrorKind::Count));
+    }
 
     let mut problems = Vec::new();
-    for i in 0..signs.len() {
-        let op = signs.get(i).unwrap().unwrap();
+    for (i, operation) in signs.into_iter().enumerate() {
         let mut problem = Problem {
             numbers: Vec::new(),
-            operation: op,
+            operation,
         };
-        for line in &number_lines {
-            let num = line.get(i).unwrap();
-            problem.numbers.push(*num);
+        for (_, line) in &number_lines {
+            problem.numbers.push(line[i]);
         }
         problems.push(problem)
     }
@@ -57,29 +72,21 @@ fn problems(input: &str) -> IResult<&str, Vec<Problem>> {
     Ok((new_remainder, problems))
 }
 
-fn problems2(input: &str) -> IResult<&str, Vec<Problem>> {
+pub fn problems2(input: &str) -> IResult<&str, Vec<Problem>> {
     let mut number_lines = Vec::new();
     let mut sign_line = None;
     for l in input.lines() {
-        if l.contains('+') {
+        if l.contains(['+', '*']) {
             sign_line = Some(l);
             break;
         }
         number_lines.push(l);
     }
-    let sign_line = sign_line.expect("did not find the sign line");
+    let sign_line = sign_line.ok_or_else(|| failure(&input[input.len()..], ErrorKind::OneOf))?;
     let input_width = sign_line.len();
 
     // parse sign line
-    let (_, signs) = separated_list1(
-        space1,
-        map(one_of("+*"), |c| match c {
-            '+' => Some(Operation::Add),
-            '*' => Some(Operation::Multiply),
-            _ => None,
-        }),
-    )
-    .parse(sign_line)?;
+    let (_, signs) = separated_list1(space1, operation).parse(sign_line)?;
 
     // parse numbers and create the problems
     let mut signs = VecDeque::from(signs);
@@ -88,27 +95,59 @@ fn problems2(input: &str) -> IResult<&str, Vec<Problem>> {
     for i in 0..input_width {
         let mut num = String::new();
         for line in &number_lines {
-            num.push(line.chars().nth(i).unwrap());
+            // the digits are read by columns, every line must span the sign line
+            let c = line
+                .chars()
+                .nth(i)
+                .ok_or_else(|| failure(&line[line.len()..], ErrorKind::Eof))?;
+            num.push(c);
         }
         if let Ok(n) = num.trim().parse::<u64>() {
             numbers.push(n);
         } else {
             problems.push(Problem {
                 numbers: numbers.clone(),
-                operation: signs.pop_front().unwrap().unwrap(),
+                operation: signs
+                    .pop_front()
+                    .ok_or_else(|| failure(sign_line, ErrorKind::Count))?,
             });
             numbers.clear();
         }
     }
     problems.push(Problem {
         numbers,
-        operation: signs.pop_front().unwrap().unwrap(),
+        operation: signs
+            .pop_front()
+            .ok_or_else(|| failure(sign_line, ErrorKind::Count))?,
     });
 
     Ok(("", problems))
 }
 
-fn part1(problems: &[Problem]) -> u64 {
+/// Checks that every line spans the sign line, the numbers being read by columns.
+///
+/// Lines of different widths mean that the spaces aligning the columns were lost.
+fn check_alignment(input: &str) -> Result<()> {
+    let width = input.lines().last().map_or(0, |l| l.chars().count());
+    let mut offset = 0;
+    for line in input.lines() {
+        let line_width = line.chars().count();
+        if line_width != width {
+            return Err(Error::at_offset(
+                input,
+                offset + line.len(),
+                format!(
+                    "the line is {line_width} columns wide instead of {width}, \
+                     the spaces aligning the columns may have been stripped"
+                ),
+            ));
+        }
+        offset += line.len() + 1;
+    }
+    Ok(())
+}
+
+pub fn part1(problems: &[Problem]) -> u64 {
     let mut res = 0;
     for problem in problems {
         res += match problem.operation {
@@ -119,7 +158,7 @@ fn part1(problems: &[Problem]) -> u64 {
     res
 }
 
-fn part2(problems: &[Problem]) -> u64 {
+pub fn part2(problems: &[Problem]) -> u64 {
     let mut res = 0;
     for problem in problems {
         res += match problem.operation {
@@ -130,24 +169,43 @@ fn part2(problems: &[Problem]) -> u64 {
     res
 }
 
-pub fn run(input: &str) {
-    let (_, problems) = problems(input).expect("Could not parse input problems");
-    println!("=> part1 : {}", part1(&problems));
-    let (_, problems) = problems2(input).expect("Could not parse input problems");
-    println!("=> part2 : {}", part2(&problems));
+pub struct Day6;
+
+impl Solution for Day6 {
+    /// Problems read by rows for part 1 and by columns for part 2
+    type Input = (Vec<Problem>, Vec<Problem>);
+    type Output1 = u64;
+    type Output2 = u64;
+
+    const LAYOUT: Layout = Layout::Aligned;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let by_rows = parse_all(input, problems)?;
+        check_alignment(input)?;
+        let by_columns = parse_all(input, problems2)?;
+        Ok((by_rows, by_columns))
+    }
+
+    fn part1((problems, _): &Self::Input) -> Result<u64> {
+        Ok(part1(problems))
+    }
+
+    fn part2((_, problems): &Self::Input) -> Result<u64> {
+        Ok(part2(problems))
+    }
 }
 
 #[cfg(test)]
 mod tests {
 
-    use super::{part1, part2, problems, problems2};
+    use crate::examples::DAY6;
+    use crate::solution::{DynSolution, Part};
+
+    use super::{Day6, part1, part2, problems, problems2};
 
     #[test]
     fn test() {
-        let data = "123 328  51 64 
- 45 64  387 23 
-  6 98  215 314
-*   +   *   +  ";
+        let data = DAY6;
 
         let (_, problems) = problems(data).expect("Could not parse the input data");
         assert_eq!(part1(&problems), 4277556);
@@ -155,5 +213,29 @@ mod tests {
         let (_, problems) = problems2(data).expect("Could not parse the input data");
         dbg!(&problems);
         assert_eq!(part2(&problems), 3263827);
+
+        let crlf = format!("{}\r\n", data.replace('\n', "\r\n"));
+        let answers = Day6
+            .solve(&crlf, Part::Both)
+            .expect("Could not solve the CRLF input");
+        assert_eq!(
+            answers.part2.map(|a| a.to_string()),
+            Some("3263827".to_string())
+        );
+    }
+
+    #[test]
+    fn stripped_layout() {
+        let data = "123 328  51 64
+ 45 64  387 23
+  6 98  215 314
+*   +   *   +  ";
+        let err = Day6
+            .solve(data, Part::Both)
+            .expect_err("the layout is damaged");
+        assert!(
+            err.to_string()
+                .starts_with("parse error at line 1, column 15")
+        );
     }
 }
@@ -1,30 +1,66 @@
-use std::{
-    collections::{HashMap, HashSet},
-    str::Lines,
-};
-
-fn part1(input: &str) -> u64 {
-    let mut lines = input.lines();
-    let mut current_indexes: HashSet<_> = lines
-        .next()
-        .unwrap()
-        .chars()
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+
+use crate::error::{Error, Result};
+use crate::grid::{Grid, Position};
+use crate::solution::Solution;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tile {
+    Empty,
+    Start,
+    Splitter,
+}
+
+impl Tile {
+    pub fn from_char(c: char) -> Option<Tile> {
+        match c {
+            '.' => Some(Tile::Empty),
+            'S' => Some(Tile::Start),
+            '^' => Some(Tile::Splitter),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Tile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let c = match self {
+            Tile::Empty => '.',
+            Tile::Start => 'S',
+            Tile::Splitter => '^',
+        };
+        write!(f, "{c}")
+    }
+}
+
+pub fn manifold(input: &str) -> Result<Grid<Tile>> {
+    Grid::parse(input, Tile::from_char)
+}
+
+/// Column of the starting point on the first row.
+fn start(manifold: &Grid<Tile>) -> Result<usize> {
+    manifold
+        .row(0)
+        .and_then(|row| row.iter().position(|t| *t == Tile::Start))
+        .ok_or_else(|| Error::InvalidInput("the first line has no starting point".to_string()))
+}
+
+pub fn part1(manifold: &Grid<Tile>) -> Result<u64> {
+    let mut current_indexes: HashSet<_> = manifold
+        .row(0)
+        .ok_or_else(|| Error::InvalidInput("the manifold has no line".to_string()))?
+        .iter()
         .enumerate()
-        .filter(|(_, c)| *c == 'S')
+        .filter(|(_, t)| **t == Tile::Start)
         .map(|(i, _)| i)
         .collect();
 
     let mut res = 0;
-    for line in lines {
-        let deflectors: Vec<_> = line
-            .chars()
-            .enumerate()
-            .filter(|(_, c)| *c == '^')
-            .map(|(i, _)| i)
-            .collect();
+    for row in manifold.rows().skip(1) {
         let mut new_indexes = HashSet::new();
         for index in current_indexes.iter() {
-            if deflectors.contains(index) {
+            if row[*index] == Tile::Splitter {
                 res += 1;
                 new_indexes.insert(index - 1);
                 new_indexes.insert(index + 1);
@@ -34,113 +70,82 @@ fn part1(input: &str) -> u64 {
         }
         current_indexes = new_indexes;
     }
-    res
-}
-
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
-struct Position {
-    index: usize,
-    line_number: u64,
-}
-
-impl Position {
-    fn left(&self) -> Self {
-        Position {
-            index: self.index - 1,
-            line_number: self.line_number + 1,
-        }
-    }
-
-    fn right(&self) -> Self {
-        Position {
-            index: self.index + 1,
-            line_number: self.line_number + 1,
-        }
-    }
-
-    fn center(&self) -> Self {
-        Position {
-            index: self.index,
-            line_number: self.line_number + 1,
-        }
-    }
+    Ok(res)
 }
 
 fn resolve_subgraph(
-    start_position: Position,
-    lines: &mut Lines,
+    (row, column): Position,
+    manifold: &Grid<Tile>,
     known: &mut HashMap<Position, u64>,
 ) -> u64 {
-    if let Some(res) = known.get(&start_position) {
+    if let Some(res) = known.get(&(row, column)) {
         return *res;
     }
     let mut res = 0;
-    if let Some(line) = lines.next() {
-        if line
-            .chars()
-            .nth(start_position.index)
-            .expect("unexpected index")
-            == '^'
-        {
+    if let Some(tile) = manifold.get((row + 1, column)) {
+        if *tile == Tile::Splitter {
             res += 1;
-            res += resolve_subgraph(start_position.left(), &mut lines.clone(), known);
-            res += resolve_subgraph(start_position.right(), &mut lines.clone(), known);
+            res += resolve_subgraph((row + 1, column - 1), manifold, known);
+            res += resolve_subgraph((row + 1, column + 1), manifold, known);
         } else {
-            res += resolve_subgraph(start_position.center(), &mut lines.clone(), known);
+            res += resolve_subgraph((row + 1, column), manifold, known);
         }
     }
-    known.insert(start_position, res);
+    known.insert((row, column), res);
     res
 }
 
-fn part2(input: &str) -> u64 {
-    let mut lines = input.lines();
-    let start_index = lines
-        .next()
-        .unwrap()
-        .find('S')
-        .expect("did not find starting point");
-
-    resolve_subgraph(
-        Position {
-            index: start_index,
-            line_number: 0,
-        },
-        &mut lines,
-        &mut HashMap::new(),
-    ) + 1
+pub fn part2(manifold: &Grid<Tile>) -> Result<u64> {
+    let start_index = start(manifold)?;
+
+    Ok(resolve_subgraph((0, start_index), manifold, &mut HashMap::new()) + 1)
 }
 
-pub fn run(input: &str) {
-    println!("=> part1 : {}", part1(input));
-    println!("=> part2 : {}", part2(input));
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input = Grid<Tile>;
+    type Output1 = u64;
+    type Output2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let manifold = manifold(input)?;
+        start(&manifold)?;
+        let last = manifold.width().saturating_sub(1);
+        for (i, row) in manifold.rows().enumerate().skip(1) {
+            if row[0] == Tile::Splitter || row[last] == Tile::Splitter {
+                return Err(Error::InvalidInput(format!(
+                    "line {} has a splitter on the border",
+                    i + 1
+                )));
+            }
+        }
+        Ok(manifold)
+    }
+
+    fn part1(input: &Self::Input) -> Result<u64> {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<u64> {
+        part2(input)
+    }
 }
 
 #[cfg(test)]
 mod tests {
 
-    use super::{part1, part2};
+    use crate::examples::DAY7;
+
+    use super::{manifold, part1, part2};
 
     #[test]
     fn test() {
-        let data = ".......S.......
-...............
-.......^.......
-...............
-......^.^......
-...............
-.....^.^.^.....
-...............
-....^.^...^....
-...............
-...^.^...^.^...
-...............
-..^...^.....^..
-...............
-.^.^.^.^.^...^.
-...............";
-
-        assert_eq!(part1(&data), 21);
-        assert_eq!(part2(&data), 40);
+        let data = DAY7;
+
+        let manifold = manifold(data).expect("Could not parse the manifold");
+        assert_eq!(part1(&manifold).expect("Could not solve part 1"), 21);
+        assert_eq!(part2(&manifold).expect("Could not solve part 2"), 40);
+        assert_eq!(manifold.to_string(), data);
     }
 }
@@ -1,209 +1,108 @@
-use std::cmp::Reverse;
-use std::collections::BinaryHeap;
-use std::collections::HashSet;
-
 use nom::IResult;
 use nom::Parser;
 use nom::character::complete::{char, i64};
 use nom::multi::separated_list1;
 
+use crate::error::{Error, Result, parse_all};
+use crate::solution::Solution;
+use crate::union_find::UnionFind;
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
-struct Box {
-    x: i64,
-    y: i64,
-    z: i64,
+pub struct Box {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
 }
 
 impl Box {
-    fn distance(&self, other: &Self) -> f64 {
-        (((self.x - other.x).pow(2) + (self.y - other.y).pow(2) + (self.z - other.z).pow(2)) as f64)
-            .sqrt()
-    }
-}
-
-#[derive(Debug, PartialEq)]
-struct Distance {
-    boxes: (Box, Box),
-    distance: f64,
-}
-impl Eq for Distance {}
-
-impl PartialOrd for Distance {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
+    pub fn distance(&self, other: &Self) -> f64 {
+        (self.squared_distance(other) as f64).sqrt()
     }
-}
 
-impl Ord for Distance {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        if let Some(cmp) = self.distance.partial_cmp(&other.distance) {
-            cmp
-        } else {
-            self.boxes.0.cmp(&other.boxes.0)
-        }
+    /// Square of the distance, enough to compare distances without rounding.
+    pub fn squared_distance(&self, other: &Self) -> i64 {
+        (self.x - other.x).pow(2) + (self.y - other.y).pow(2) + (self.z - other.z).pow(2)
     }
 }
 
-fn normalize(b1: Box, b2: Box) -> (Box, Box) {
-    if b1 <= b2 { (b1, b2) } else { (b2, b1) }
-}
-
-fn position(input: &str) -> IResult<&str, Box> {
-    let (remainder, coords) = separated_list1(char(','), i64).parse(input)?;
-    Ok((
-        remainder,
-        Box {
-            x: *coords.first().expect("X not found"),
-            y: *coords.get(1).expect("Y not found"),
-            z: *coords.get(2).expect("Z not found"),
-        },
-    ))
+pub fn position(input: &str) -> IResult<&str, Box> {
+    let (remainder, (x, _, y, _, z)) = (i64, char(','), i64, char(','), i64).parse(input)?;
+    Ok((remainder, Box { x, y, z }))
 }
-fn positions(input: &str) -> IResult<&str, Vec<Box>> {
+pub fn positions(input: &str) -> IResult<&str, Vec<Box>> {
     separated_list1(char('\n'), position).parse(input)
 }
 
-fn distances(boxes: &[Box]) -> BinaryHeap<Reverse<Distance>> {
-    // Compute all the distances
-    let mut distances = BinaryHeap::new();
-    let mut seen: HashSet<(Box, Box)> = HashSet::new();
-    boxes.iter().for_each(|b1| {
-        boxes.iter().for_each(|b2| {
-            if b1 != b2 {
-                let pair = normalize(b1.clone(), b2.clone());
-                if seen.insert(pair.clone()) {
-                    let distance = Distance {
-                        boxes: pair,
-                        distance: b1.distance(b2),
-                    };
-                    distances.push(Reverse(distance));
-                }
-            }
-        });
-    });
-    distances
+/// Every pair of boxes as indexes, from the closest to the farthest.
+fn pairs(boxes: &[Box]) -> Vec<(usize, usize)> {
+    let mut pairs: Vec<_> = (0..boxes.len())
+        .flat_map(|i| (i + 1..boxes.len()).map(move |j| (i, j)))
+        .collect();
+    pairs.sort_by_key(|(i, j)| boxes[*i].squared_distance(&boxes[*j]));
+    pairs
 }
 
-fn part1(iteration: u64, boxes: &[Box]) -> u64 {
-    let mut distances = distances(boxes);
-
+pub fn part1(iteration: u64, boxes: &[Box]) -> Result<u64> {
     // Create the circuits
-    let mut circuits: Vec<HashSet<Box>> = Vec::new();
-    for _ in 0..iteration {
-        if let Some(d) = distances.pop() {
-            let b1 = d.0.boxes.0;
-            let b2 = d.0.boxes.1;
-            let mut possible = circuits
-                .iter_mut()
-                .filter(|c| c.contains(&b1) || c.contains(&b2))
-                .collect::<Vec<_>>();
-            match possible.len() {
-                1 => {
-                    possible[0].insert(b1);
-                    possible[0].insert(b2);
-                }
-                2 => {
-                    let second = possible.pop().unwrap();
-                    possible[0].extend(second.iter().cloned());
-                    second.clear();
-                }
-                0 => {
-                    let mut circuit = HashSet::new();
-                    circuit.insert(b1);
-                    circuit.insert(b2);
-                    circuits.push(circuit);
-                }
-                _ => {
-                    println!("Unexpected possible length");
-                    dbg!(&possible);
-                }
-            };
-        }
+    let mut circuits = UnionFind::new(boxes.len());
+    for (i, j) in pairs(boxes).into_iter().take(iteration as usize) {
+        circuits.union(i, j);
     }
-    circuits.sort_by_key(|c1| c1.len());
-    let mut res = 1;
-    for _ in 0..3 {
-        res *= circuits.pop().unwrap().len();
+    let mut sizes = circuits.component_sizes();
+    if sizes.len() < 3 {
+        return Err(Error::NoSolution(
+            "less than 3 circuits were created".to_string(),
+        ));
     }
-    res as u64
+    sizes.sort_unstable_by(|a, b| b.cmp(a));
+    Ok(sizes[..3].iter().product::<usize>() as u64)
 }
 
-fn part2(boxes: &[Box]) -> i64 {
-    let mut distances = distances(boxes);
-
-    let mut circuits: Vec<HashSet<Box>> = Vec::new();
-    loop {
-        if let Some(d) = distances.pop() {
-            let b1 = d.0.boxes.0.clone();
-            let b2 = d.0.boxes.1.clone();
-            let mut possible = circuits
-                .iter_mut()
-                .filter(|c| c.contains(&b1) || c.contains(&b2))
-                .collect::<Vec<_>>();
-            match possible.len() {
-                1 => {
-                    possible[0].insert(b1);
-                    possible[0].insert(b2);
-                }
-                2 => {
-                    let second = possible.pop().unwrap();
-                    possible[0].extend(second.iter().cloned());
-                    second.clear();
-                }
-                0 => {
-                    let mut circuit = HashSet::new();
-                    circuit.insert(b1);
-                    circuit.insert(b2);
-                    circuits.push(circuit);
-                }
-                _ => {
-                    println!("Unexpected possible length");
-                    dbg!(&possible);
-                }
-            };
-            if circuits.iter().any(|c| c.len() == boxes.len()) {
-                return d.0.boxes.0.x * d.0.boxes.1.x;
-            }
+pub fn part2(boxes: &[Box]) -> Result<i64> {
+    let mut circuits = UnionFind::new(boxes.len());
+    for (i, j) in pairs(boxes) {
+        if circuits.union(i, j) && circuits.components() == 1 {
+            return Ok(boxes[i].x * boxes[j].x);
         }
     }
+    Err(Error::NoSolution(
+        "the boxes never form a single circuit".to_string(),
+    ))
 }
 
-pub fn run(input: &str) {
-    let (_, boxes) = positions(input).expect("Could not parse input problems");
-    println!("=> part1 : {}", part1(1000, &boxes));
-    println!("=> part2 : {}", part2(&boxes));
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Input = Vec<Box>;
+    type Output1 = u64;
+    type Output2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_all(input, positions)
+    }
+
+    fn part1(boxes: &Self::Input) -> Result<u64> {
+        part1(1000, boxes)
+    }
+
+    fn part2(boxes: &Self::Input) -> Result<i64> {
+        part2(boxes)
+    }
 }
 
 #[cfg(test)]
 mod tests {
 
+    use crate::examples::DAY8;
+
     use super::{part1, part2, positions};
 
     #[test]
     fn test() {
-        let data = "162,817,812
-57,618,57
-906,360,560
-592,479,940
-352,342,300
-466,668,158
-542,29,236
-431,825,988
-739,650,466
-52,470,668
-216,146,977
-819,987,18
-117,168,530
-805,96,715
-346,949,466
-970,615,88
-941,993,340
-862,61,35
-984,92,344
-425,690,689";
+        let data = DAY8;
 
         let (_, boxes) = positions(data).expect("Could not parse the input data");
-        assert_eq!(part1(10, &boxes), 40);
-        assert_eq!(part2(&boxes), 25272);
+        assert_eq!(part1(10, &boxes).expect("Could not solve part 1"), 40);
+        assert_eq!(part2(&boxes).expect("Could not solve part 2"), 25272);
     }
 }
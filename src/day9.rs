@@ -1,554 +1,206 @@
-use std::collections::BinaryHeap;
-use std::collections::HashMap;
-use std::collections::HashSet;
-use std::fmt::Display;
-use std::hash::Hash;
-use std::time;
-
 use nom::IResult;
 use nom::Parser;
 use nom::character::complete::{char, u64};
 use nom::multi::separated_list1;
+use nom::sequence::separated_pair;
 
 use itertools::Itertools;
 use rayon::prelude::*;
 
-#[derive(Debug, Eq, PartialOrd, Ord, Clone, Default)]
-struct Tile {
-    x: u64,
-    y: u64,
-    x_compressed: u64,
-    y_compressed: u64,
-}
+use crate::compression::CompressedSpace;
+use crate::error::{Error, Result, parse_all};
+use crate::geometry::{Point, Polygon};
+use crate::grid::Grid;
+use crate::solution::Solution;
+use crate::svg::Svg;
 
-impl PartialEq for Tile {
-    fn eq(&self, other: &Self) -> bool {
-        self.x == other.x && self.y == other.y
-    }
+pub fn tile(input: &str) -> IResult<&str, Point> {
+    let (remainder, (x, y)) = separated_pair(u64, char(','), u64).parse(input)?;
+    Ok((remainder, Point::new(x, y)))
 }
 
-impl Hash for Tile {
-    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        self.x.hash(state);
-        self.y.hash(state);
-    }
+pub fn tiles(input: &str) -> IResult<&str, Vec<Point>> {
+    separated_list1(char('\n'), tile).parse(input)
 }
 
-impl Display for Tile {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Tile [{} - {}]", self.x, self.y)
-    }
+/// Compression of both axes of the polygon, with a gap cell between two values that
+/// are not adjacent so that the inside of thin parts is kept.
+fn get_compression(polygon: &Polygon) -> CompressedSpace<2> {
+    let points: Vec<_> = polygon.vertices().iter().map(|t| [t.x, t.y]).collect();
+    CompressedSpace::new(&points, true)
 }
 
-impl Tile {
-    fn new(x: u64, y: u64) -> Self {
-        Tile {
-            x,
-            y,
-            ..Default::default()
-        }
-    }
-    fn new_compressed(
-        x: u64,
-        y: u64,
-        x_map_inv: &HashMap<u64, u64>,
-        y_map_inv: &HashMap<u64, u64>,
-    ) -> Self {
-        Tile {
-            x: x_map_inv[&x],
-            y: y_map_inv[&y],
-            x_compressed: x,
-            y_compressed: y,
-        }
-    }
-
-    fn apply_compression(&mut self, x_map: &HashMap<u64, u64>, y_map: &HashMap<u64, u64>) {
-        self.x_compressed = x_map[&self.x];
-        self.y_compressed = y_map[&self.y];
-    }
-
-    fn area_size(&self, other: &Self) -> u64 {
-        (self.x.abs_diff(other.x) + 1) * (self.y.abs_diff(other.y) + 1)
-    }
-
-    fn is_on_polygon_edge(&self, polygon: &[Tile]) -> bool {
-        let n = polygon.len();
-
-        for i in 0..n {
-            let j = (i + 1) % n;
-            let xi = polygon[i].x_compressed;
-            let yi = polygon[i].y_compressed;
-            let xj = polygon[j].x_compressed;
-            let yj = polygon[j].y_compressed;
-
-            if (xi == xj
-                && xi == self.x_compressed
-                && self.y_compressed >= yi.min(yj)
-                && self.y_compressed <= yi.max(yj))
-                || (yi == yj
-                    && yi == self.y_compressed
-                    && self.x_compressed >= xi.min(xj)
-                    && self.x_compressed <= xi.max(xj))
-            {
-                return true;
-            }
-        }
-        false
-    }
-
-    fn is_in_polygon(&self, polygon: &[Tile]) -> Result<bool, EdgeAligned> {
-        let mut inside = false;
-        let n = polygon.len();
-
-        for i in 0..n {
-            let j = (i + 1) % n;
-            let xi = polygon[i].x_compressed;
-            let yi = polygon[i].y_compressed;
-            let xj = polygon[j].x_compressed;
-            let yj = polygon[j].y_compressed;
-
-            if (xi == xj
-                && xi == self.x_compressed
-                && self.y_compressed >= yi.min(yj)
-                && self.y_compressed <= yi.max(yj))
-                || (yi == yj
-                    && yi == self.y_compressed
-                    && self.x_compressed >= xi.min(xj)
-                    && self.x_compressed <= xi.max(xj))
-            {
-                return Ok(true);
-            }
-
-            if xi == xj
-                && self.y_compressed >= yi.min(yj)
-                && self.y_compressed <= yi.max(yj)
-                && self.x_compressed < xi
-            {
-                inside = !inside;
-            } else if yi == yj && self.y_compressed == yi && self.x_compressed < xi.min(xj) {
-                println!("WARNING : This is the edge case where we don't know");
-                return Err(EdgeAligned {});
-            }
-        }
-        Ok(inside)
-    }
-
-    fn get_edges(&self, other: &Self) -> Vec<Tile> {
-        let mut res = Vec::new();
-        for i in self.x.min(other.x)..self.x.max(other.x) {
-            res.push(Self::new(i, self.y));
-            res.push(Self::new(i, other.y));
-        }
-        for i in self.y.min(other.y)..self.y.max(other.y) {
-            res.push(Self::new(self.x, i));
-            res.push(Self::new(other.x, i));
-        }
-        res
-    }
+/// The polygon with compressed coordinates, which keeps the order of the values and so
+/// the shape of the polygon.
+fn compress(polygon: &Polygon, space: &CompressedSpace<2>) -> Polygon {
+    Polygon::new(
+        polygon
+            .vertices()
+            .iter()
+            .map(|t| {
+                let [x, y] = space
+                    .compress(&[t.x, t.y])
+                    .expect("a vertex of the polygon");
+                Point::new(x as u64, y as u64)
+            })
+            .collect(),
+    )
+    .expect("the compression keeps the polygon valid")
+}
 
-    fn get_compressed_edges(
-        &self,
-        other: &Self,
-        x_map_inv: &HashMap<u64, u64>,
-        y_map_inv: &HashMap<u64, u64>,
-    ) -> Vec<Tile> {
-        let mut res = Vec::new();
-        for i in
-            self.x_compressed.min(other.x_compressed)..=self.x_compressed.max(other.x_compressed)
-        {
-            res.push(Self::new_compressed(
-                i,
-                self.y_compressed,
-                x_map_inv,
-                y_map_inv,
-            ));
-            res.push(Self::new_compressed(
-                i,
-                other.y_compressed,
-                x_map_inv,
-                y_map_inv,
-            ));
-        }
-        for i in
-            self.y_compressed.min(other.y_compressed)..=self.y_compressed.max(other.y_compressed)
-        {
-            res.push(Self::new_compressed(
-                self.x_compressed,
-                i,
-                x_map_inv,
-                y_map_inv,
-            ));
-            res.push(Self::new_compressed(
-                other.x_compressed,
-                i,
-                x_map_inv,
-                y_map_inv,
-            ));
+/// Number of tiles out of the polygon above and on the left of each compressed tile,
+/// with one more row and column so that the first ones are all zeros.
+fn outside_prefix_sums(raster: &Grid<bool>) -> Grid<u32> {
+    let mut rows = vec![vec![0; raster.width() + 1]; raster.height() + 1];
+    for (y, row) in raster.rows().enumerate() {
+        for (x, inside) in row.iter().enumerate() {
+            rows[y + 1][x + 1] = rows[y][x + 1] + rows[y + 1][x] - rows[y][x] + u32::from(!*inside);
         }
-        res
     }
+    Grid::from_rows(rows).expect("the rows have the same width")
 }
 
-#[derive(Debug)]
-struct EdgeAligned {}
-
-impl Display for EdgeAligned {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Point is aligne with an edge")
-    }
+/// Number of tiles out of the polygon in the rectangle of compressed tiles, in O(1).
+fn outside_tiles(sums: &Grid<u32>, c1: Point, c2: Point) -> u32 {
+    let (left, right) = (c1.x.min(c2.x) as usize, c1.x.max(c2.x) as usize + 1);
+    let (top, bottom) = (c1.y.min(c2.y) as usize, c1.y.max(c2.y) as usize + 1);
+    sums[(bottom, right)] + sums[(top, left)] - sums[(top, right)] - sums[(bottom, left)]
 }
 
-impl std::error::Error for EdgeAligned {}
-
-#[derive(Debug, PartialEq, Eq, Hash)]
-struct Rectangle {
-    tiles: (Tile, Tile),
-    area: u64,
+/// Rectangle having two red tiles as opposite corners.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rectangle {
+    pub corners: (Point, Point),
+    pub area: u64,
 }
 
-impl PartialOrd for Rectangle {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
-    }
+/// Largest rectangle with red corners, wherever its tiles are.
+pub fn largest_rectangle(tiles: &[Point]) -> Result<Rectangle> {
+    tiles
+        .iter()
+        .tuple_combinations()
+        .map(|(t1, t2)| Rectangle {
+            corners: (*t1, *t2),
+            area: t1.rectangle_area(t2),
+        })
+        .max_by_key(|r| (r.area, r.corners))
+        .ok_or_else(|| Error::InvalidInput("at least 2 red tiles are needed".to_string()))
 }
 
-impl Ord for Rectangle {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.area.cmp(&other.area)
-    }
+pub fn part1(tiles: &[Point]) -> Result<u64> {
+    Ok(largest_rectangle(tiles)?.area)
 }
 
-fn tile(input: &str) -> IResult<&str, Tile> {
-    let (remainder, coords) = separated_list1(char(','), u64).parse(input)?;
-    Ok((
-        remainder,
-        Tile::new(
-            *coords.first().expect("X not found"),
-            *coords.get(1).expect("Y not found"),
-        ),
-    ))
-}
+/// Largest rectangle with red corners whose tiles are all in the polygon.
+pub fn largest_inscribed_rectangle(polygon: &Polygon) -> Result<Rectangle> {
+    // Compress red tiles
+    let space = get_compression(polygon);
+    let compressed = compress(polygon, &space);
 
-fn tiles(input: &str) -> IResult<&str, Vec<Tile>> {
-    separated_list1(char('\n'), tile).parse(input)
-}
+    // Tell the compressed tiles out of the polygon in any rectangle at once
+    let sums = outside_prefix_sums(&compressed.rasterize());
 
-#[allow(clippy::type_complexity)]
-fn get_compression(
-    tiles: &[Tile],
-) -> (
-    HashMap<u64, u64>,
-    HashMap<u64, u64>,
-    HashMap<u64, u64>,
-    HashMap<u64, u64>,
-) {
-    let mut x_values = HashSet::new();
-    let mut y_values = HashSet::new();
-    tiles.iter().for_each(|t| {
-        x_values.insert(t.x);
-        y_values.insert(t.y);
-    });
-
-    let mut x_values = Vec::from_iter(x_values);
-    let mut y_values = Vec::from_iter(y_values);
-    x_values.sort();
-    y_values.sort();
-
-    // compression maps
-    let mut x_map = HashMap::new();
-    let mut y_map = HashMap::new();
-
-    // decompression maps
-    let mut x_map_inv = HashMap::new();
-    let mut y_map_inv = HashMap::new();
-
-    x_values.iter().enumerate().for_each(|(i, x)| {
-        x_map.insert(*x, i as u64);
-        x_map_inv.insert(i as u64, *x);
-    });
-    y_values.iter().enumerate().for_each(|(i, y)| {
-        y_map.insert(*y, i as u64);
-        y_map_inv.insert(i as u64, *y);
-    });
-
-    (x_map, y_map, x_map_inv, y_map_inv)
+    polygon
+        .vertices()
+        .iter()
+        .zip(compressed.vertices())
+        .tuple_combinations() // create tuple of tiles
+        .par_bridge() // bridge to parallel iteration
+        .filter(|((_, c1), (_, c2))| outside_tiles(&sums, **c1, **c2) == 0)
+        .map(|((t1, _), (t2, _))| Rectangle {
+            corners: (*t1, *t2),
+            area: t1.rectangle_area(t2),
+        })
+        // the corners break ties so that the answer does not depend on the threads
+        .max_by_key(|r| (r.area, r.corners))
+        .ok_or_else(|| Error::InvalidInput("at least 2 red tiles are needed".to_string()))
 }
 
-fn draw(red_tiles: &[Tile]) {
-    let max_x = red_tiles.iter().map(|t| t.x).max().unwrap();
-    let max_y = red_tiles.iter().map(|t| t.y).max().unwrap();
-    let min_x = red_tiles.iter().map(|t| t.x).min().unwrap();
-    let min_y = red_tiles.iter().map(|t| t.y).min().unwrap();
-
-    for j in min_y..=max_y {
-        for i in min_x..=max_x {
-            let t = Tile {
-                x: i,
-                y: j,
-                ..Default::default()
-            };
-            if red_tiles.contains(&t) {
-                print!("X");
-            } else {
-                print!(".");
-            }
-        }
-        println!();
-    }
+pub fn part2(polygon: &Polygon) -> Result<u64> {
+    Ok(largest_inscribed_rectangle(polygon)?.area)
 }
 
-fn draw_compressed(
-    red_tiles: &[Tile],
-    edges: Option<&[Tile]>,
-    x_map_inv: &HashMap<u64, u64>,
-    y_map_inv: &HashMap<u64, u64>,
-) {
-    let max_x = red_tiles.iter().map(|t| t.x_compressed).max().unwrap();
-    let max_y = red_tiles.iter().map(|t| t.y_compressed).max().unwrap();
-    let min_x = red_tiles.iter().map(|t| t.x_compressed).min().unwrap();
-    let min_y = red_tiles.iter().map(|t| t.y_compressed).min().unwrap();
-
-    let edges: Vec<Tile> = match edges {
-        Some(e) => e
-            .iter()
-            .map(|ee| Tile {
-                x: x_map_inv[&ee.x_compressed],
-                y: y_map_inv[&ee.y_compressed],
-                x_compressed: ee.x_compressed,
-                y_compressed: ee.y_compressed,
-            })
-            .collect(),
-        None => Vec::new(),
-    };
-
-    println!();
-    for j in min_y..=max_y {
-        for i in min_x..=max_x {
-            let t = Tile {
-                x: x_map_inv[&i],
-                y: y_map_inv[&j],
-                x_compressed: i,
-                y_compressed: j,
-            };
-            if edges.contains(&t) && red_tiles.contains(&t) {
-                print!("X");
-            } else if edges.contains(&t) {
-                print!("O");
-            } else if red_tiles.contains(&t) {
-                print!("#");
-            } else {
-                print!(".");
+/// Draws the polygon in real coordinates over the tiles found inside it, with the
+/// rectangles found by both parts. Each tile is a unit square.
+pub fn render(polygon: &Polygon, largest: &Rectangle, inscribed: &Rectangle) -> Result<String> {
+    let space = get_compression(polygon);
+    let raster = compress(polygon, &space).rasterize();
+    let (xs, ys) = (space.axis(0), space.axis(1));
+
+    let too_large = || Error::InvalidInput("the polygon is too large to be drawn".to_string());
+    // the tiles spanned by the polygon, and some room around them
+    let width = xs.span(0, xs.len() - 1).ok_or_else(too_large)?;
+    let height = ys.span(0, ys.len() - 1).ok_or_else(too_large)?;
+    let margin = (width.max(height) / 50).max(1);
+    let view = (
+        xs.decompress(0).unwrap_or_default().saturating_sub(margin),
+        ys.decompress(0).unwrap_or_default().saturating_sub(margin),
+        width + 2 * margin,
+        height + 2 * margin,
+    );
+    let mut svg = Svg::new(view.0, view.1, view.2, view.3);
+    svg.rect(view.0, view.1, view.2, view.3, r#"fill="white""#);
+
+    // the inside as found on the compressed grid, one rectangle per run of a row
+    for (y, row) in raster.rows().enumerate() {
+        for (inside, run) in &row.iter().enumerate().chunk_by(|(_, inside)| **inside) {
+            let run: Vec<_> = run.map(|(x, _)| x).collect();
+            if inside {
+                let (first, last) = (run[0], run[run.len() - 1]);
+                svg.rect(
+                    xs.decompress(first).expect("a compressed column"),
+                    ys.decompress(y).expect("a compressed row"),
+                    xs.span(first, last).ok_or_else(too_large)?,
+                    ys.width(y).ok_or_else(too_large)?,
+                    r##"fill="#7ec87e""##,
+                );
             }
         }
-        println!();
     }
-}
-fn draw_compressed_area(
-    red_tiles: &[Tile],
-    area: &[Tile],
-    x_map_inv: &HashMap<u64, u64>,
-    y_map_inv: &HashMap<u64, u64>,
-) {
-    let max_x = red_tiles.iter().map(|t| t.x_compressed).max().unwrap();
-    let max_y = red_tiles.iter().map(|t| t.y_compressed).max().unwrap();
-    let min_x = red_tiles.iter().map(|t| t.x_compressed).min().unwrap();
-    let min_y = red_tiles.iter().map(|t| t.y_compressed).min().unwrap();
-
-    println!();
-    for j in min_y..=max_y {
-        for i in min_x..=max_x {
-            let t = Tile {
-                x: x_map_inv[&i],
-                y: y_map_inv[&j],
-                x_compressed: i,
-                y_compressed: j,
-            };
-            if area.contains(&t) && red_tiles.contains(&t) {
-                print!("X");
-            } else if area.contains(&t) {
-                print!("O");
-            } else if red_tiles.contains(&t) {
-                print!("#");
-            } else {
-                print!(".");
-            }
-        }
-        println!();
-    }
-}
 
-// Depth First Seek implementation
-fn get_tiles_in_area(
-    tiles: &[Tile],
-    x_map_inv: &HashMap<u64, u64>,
-    y_map_inv: &HashMap<u64, u64>,
-) -> HashSet<Tile> {
-    // find a point inside the polygon that is not on an edge
-    // we do not consider points that are aligned with an horizontal edge
-    let mut start = None;
-    for i in 0..tiles.len() {
-        let ne = Tile::new_compressed(
-            tiles[i].x_compressed.saturating_add(1),
-            tiles[i].y_compressed.saturating_sub(1),
-            x_map_inv,
-            y_map_inv,
-        );
-        let se = Tile::new_compressed(
-            tiles[i].x_compressed.saturating_add(1),
-            tiles[i].y_compressed.saturating_add(1),
-            x_map_inv,
-            y_map_inv,
-        );
-        let nw = Tile::new_compressed(
-            tiles[i].x_compressed.saturating_sub(1),
-            tiles[i].y_compressed.saturating_sub(1),
-            x_map_inv,
-            y_map_inv,
-        );
-        let sw = Tile::new_compressed(
-            tiles[i].x_compressed.saturating_sub(1),
-            tiles[i].y_compressed.saturating_add(1),
-            x_map_inv,
-            y_map_inv,
+    let style = |color: &str| {
+        format!(
+            r#"fill="{color}" fill-opacity="0.25" stroke="{color}" stroke-width="3" vector-effect="non-scaling-stroke""#
+        )
+    };
+    for (rectangle, color) in [(largest, "#1f5fbf"), (inscribed, "#d4a017")] {
+        let (t1, t2) = rectangle.corners;
+        svg.rect(
+            t1.x.min(t2.x),
+            t1.y.min(t2.y),
+            t1.x.abs_diff(t2.x) + 1,
+            t1.y.abs_diff(t2.y) + 1,
+            &style(color),
         );
-        start = if ne.is_in_polygon(tiles).unwrap_or(false) && !ne.is_on_polygon_edge(tiles) {
-            Some(ne)
-        } else if se.is_in_polygon(tiles).unwrap_or(false) && !se.is_on_polygon_edge(tiles) {
-            Some(se)
-        } else if nw.is_in_polygon(tiles).unwrap_or(false) && !nw.is_on_polygon_edge(tiles) {
-            Some(nw)
-        } else if sw.is_in_polygon(tiles).unwrap_or(false) && !sw.is_on_polygon_edge(tiles) {
-            Some(sw)
-        } else {
-            continue;
-        };
-
-        if start.is_some() {
-            break;
-        }
     }
-    let start = start.expect("Did not find a starting point");
-
-    // And now we visit all the tiles and check if we reached an edge
-    let mut visited = HashSet::new();
-    let mut stack = vec![start];
-    while let Some(node) = stack.pop() {
-        if visited.insert(node.clone()) && !node.is_on_polygon_edge(tiles) {
-            stack.push(Tile::new_compressed(
-                node.x_compressed.saturating_sub(1),
-                node.y_compressed,
-                x_map_inv,
-                y_map_inv,
-            ));
-            stack.push(Tile::new_compressed(
-                node.x_compressed.saturating_add(1),
-                node.y_compressed,
-                x_map_inv,
-                y_map_inv,
-            ));
-            stack.push(Tile::new_compressed(
-                node.x_compressed,
-                node.y_compressed.saturating_add(1),
-                x_map_inv,
-                y_map_inv,
-            ));
-            stack.push(Tile::new_compressed(
-                node.x_compressed,
-                node.y_compressed.saturating_sub(1),
-                x_map_inv,
-                y_map_inv,
-            ));
-            stack.push(Tile::new_compressed(
-                node.x_compressed.saturating_sub(1),
-                node.y_compressed.saturating_sub(1),
-                x_map_inv,
-                y_map_inv,
-            ));
-            stack.push(Tile::new_compressed(
-                node.x_compressed.saturating_add(1),
-                node.y_compressed.saturating_sub(1),
-                x_map_inv,
-                y_map_inv,
-            ));
-            stack.push(Tile::new_compressed(
-                node.x_compressed.saturating_sub(1),
-                node.y_compressed.saturating_add(1),
-                x_map_inv,
-                y_map_inv,
-            ));
-            stack.push(Tile::new_compressed(
-                node.x_compressed.saturating_add(1),
-                node.y_compressed.saturating_add(1),
-                x_map_inv,
-                y_map_inv,
-            ));
-        }
-    }
-    visited
-}
 
-fn part1(tiles: &[Tile]) -> u64 {
-    let mut heap = BinaryHeap::new();
-    let mut seen = HashSet::new();
-    tiles.iter().for_each(|t1| {
-        tiles.iter().for_each(|t2| {
-            if t1 != t2 && seen.insert((t1, t2)) {
-                heap.push(Rectangle {
-                    tiles: ((*t1).clone(), (*t2).clone()),
-                    area: t1.area_size(t2),
-                });
-                seen.insert((t1, t2));
-                seen.insert((t2, t1));
-            }
-        });
-    });
-
-    heap.pop().unwrap().area
+    let center = |t: &Point| (t.x as f64 + 0.5, t.y as f64 + 0.5);
+    svg.polygon(
+        &polygon.vertices().iter().map(center).collect::<Vec<_>>(),
+        r##"fill="none" stroke="#c0392b" stroke-width="1" vector-effect="non-scaling-stroke""##,
+    );
+    Ok(svg.to_string())
 }
 
-fn part2(tiles: &mut [Tile]) -> u64 {
-    let t0 = time::SystemTime::now();
-
-    // Compute compression maps
-    let (x_map, y_map, x_map_inv, y_map_inv) = get_compression(tiles);
-    let t1 = time::SystemTime::now();
-    println!("{:?}", t1.duration_since(t0));
+pub struct Day9;
 
-    // Compress red tiles
-    tiles
-        .iter_mut()
-        .for_each(|t| t.apply_compression(&x_map, &y_map));
+impl Solution for Day9 {
+    type Input = Vec<Point>;
+    type Output1 = u64;
+    type Output2 = u64;
 
-    let t2 = time::SystemTime::now();
-    println!("{:?}", t2.duration_since(t1));
-    // Get the list of tiles in the area
-    let area = get_tiles_in_area(tiles, &x_map_inv, &y_map_inv);
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_all(input, tiles)
+    }
 
-    let t3 = time::SystemTime::now();
-    println!("{:?}", t3.duration_since(t2));
-    let res = tiles
-        .iter()
-        .tuple_combinations() // create tuple of tiles
-        .par_bridge() // bridge to parallel iteration
-        .map(|(t1, t2)| {
-            let edges = t1.get_compressed_edges(t2, &x_map_inv, &y_map_inv);
-            if edges.iter().all(|t| area.contains(t)) {
-                t1.area_size(t2)
-            } else {
-                0
-            }
-        })
-        .max()
-        .unwrap();
-    let t4 = time::SystemTime::now();
-    println!("{:?}", t4.duration_since(t3));
-    res
-}
+    fn part1(tiles: &Self::Input) -> Result<u64> {
+        part1(tiles)
+    }
 
-pub fn run(input: &str) {
-    let (_, mut tiles) = tiles(input).expect("Could not parse input problems");
-    println!("=> part1 : {}", part1(&tiles));
-    println!("=> part2 : {}", part2(&mut tiles));
+    /// Only part 2 needs the tiles to be the vertices of a polygon.
+    fn part2(tiles: &Self::Input) -> Result<u64> {
+        part2(&Polygon::new(tiles.clone())?)
+    }
 }
 
 #[cfg(test)]
@@ -556,81 +208,99 @@ mod tests {
 
     use std::fs;
 
-    use crate::day9::{
-        Tile, draw_compressed, draw_compressed_area, get_compression, get_tiles_in_area,
-    };
+    use itertools::Itertools;
+
+    use crate::day9::{compress, get_compression, outside_prefix_sums};
+    use crate::examples::DAY9;
+    use crate::geometry::{Point, Polygon};
+    use crate::solution::Solution;
 
-    use super::{part1, part2, tiles};
+    use super::{
+        Day9, largest_inscribed_rectangle, largest_rectangle, outside_tiles, part1, part2, render,
+        tiles,
+    };
 
     #[test]
     fn example_data() {
-        let data = "7,1
-11,1
-11,7
-9,7
-9,5
-2,5
-2,3
-7,3";
-
-        let (_, mut tiles) = tiles(data).expect("Could not parse the input data");
-        assert_eq!(part1(&tiles), 50);
-        assert_eq!(part2(&mut tiles), 24);
+        let data = DAY9;
+
+        let (_, tiles) = tiles(data).expect("Could not parse the input data");
+        let polygon = Polygon::new(tiles).expect("Could not build the polygon");
+        assert_eq!(
+            part1(polygon.vertices()).expect("Could not solve part 1"),
+            50
+        );
+        assert_eq!(part2(&polygon).expect("Could not solve part 2"), 24);
+        let best = largest_inscribed_rectangle(&polygon).expect("Could not solve part 2");
+        assert_eq!(best.corners, (Point::new(9, 5), Point::new(2, 3)));
     }
 
     #[test]
-    fn debug() {
-        let input = fs::read_to_string(format!("./resources/input9"))
-            .expect("Could not load the input file");
-        let (_, mut tiles) = tiles(&input).expect("Could not parse the input data");
-
-        let (x_map, y_map, x_map_inv, y_map_inv) = get_compression(&tiles);
-
-        // Compress red tiles
-        tiles
-            .iter_mut()
-            .for_each(|t| t.apply_compression(&x_map, &y_map));
-        let mut t1 = Tile {
-            x: 11937,
-            y: 77901,
-            ..Default::default()
-        };
-        let mut t2 = Tile {
-            x: 11204,
-            y: 77297,
-            ..Default::default()
-        };
-
-        t1.apply_compression(&x_map, &y_map);
-        t2.apply_compression(&x_map, &y_map);
+    fn scattered_tiles() {
+        // the tiles do not close a rectilinear polygon, part 1 only needs the points
+        let tiles = Day9::parse("1,1\n5,3\n2,7").expect("Could not parse the tiles");
+        assert_eq!(Day9::part1(&tiles).expect("Could not solve part 1"), 20);
+        assert!(Day9::part2(&tiles).is_err());
+    }
 
-        let edges = t1.get_compressed_edges(&t2, &x_map_inv, &y_map_inv);
-        draw_compressed(&tiles, Some(&edges), &x_map_inv, &y_map_inv);
+    #[test]
+    fn svg() {
+        let (_, tiles) = tiles(DAY9).expect("Could not parse the input data");
+        let polygon = Polygon::new(tiles).expect("Could not build the polygon");
+        let largest = largest_rectangle(polygon.vertices()).expect("Could not solve part 1");
+        let inscribed = largest_inscribed_rectangle(&polygon).expect("Could not solve part 2");
+        let svg = render(&polygon, &largest, &inscribed).expect("Could not render");
+        assert!(svg.starts_with("<svg"));
+        // the rectangles of part 1 and part 2
+        assert!(svg.contains(r##"<rect x="2" y="3" width="10" height="5" fill="#1f5fbf""##));
+        assert!(svg.contains(r##"<rect x="2" y="3" width="8" height="3" fill="#d4a017""##));
+        assert!(svg.contains(r#"<polygon points="7.5,1.5 11.5,1.5 11.5,7.5"#));
+    }
 
-        for t in edges {
-            println!("{:?} => {}", t, t.is_in_polygon(&tiles).unwrap());
-            assert!(t.is_in_polygon(&tiles).unwrap());
+    #[test]
+    fn against_brute_force() {
+        let shapes = [
+            DAY9,
+            // a U whose branches are one tile apart
+            "0,0\n3,0\n3,6\n4,6\n4,0\n8,0\n8,9\n0,9",
+            // a staircase
+            "0,0\n2,0\n2,2\n5,2\n5,5\n9,5\n9,8\n0,8",
+        ];
+        for shape in shapes {
+            let (_, tiles) = tiles(shape).expect("Could not parse the input data");
+            let polygon = Polygon::new(tiles).expect("Could not build the polygon");
+            let expected = polygon
+                .vertices()
+                .iter()
+                .tuple_combinations()
+                .filter(|(t1, t2)| {
+                    (t1.x.min(t2.x)..=t1.x.max(t2.x))
+                        .cartesian_product(t1.y.min(t2.y)..=t1.y.max(t2.y))
+                        .all(|(x, y)| polygon.contains(Point::new(x, y)))
+                })
+                .map(|(t1, t2)| t1.rectangle_area(t2))
+                .max();
+            assert_eq!(part2(&polygon).ok(), expected, "{shape}");
         }
     }
 
     #[test]
-    fn fill_area() {
-        let input = fs::read_to_string(format!("./resources/input9"))
-            .expect("Could not load the input file");
-        let (_, mut tiles) = tiles(&input).expect("Could not parse the input data");
-
-        let (x_map, y_map, x_map_inv, y_map_inv) = get_compression(&tiles);
-        tiles
-            .iter_mut()
-            .for_each(|t| t.apply_compression(&x_map, &y_map));
-
-        let area = get_tiles_in_area(&tiles, &x_map_inv, &y_map_inv);
-
-        draw_compressed_area(
-            &tiles,
-            &area.into_iter().collect::<Vec<_>>(),
-            &x_map_inv,
-            &y_map_inv,
-        );
+    fn debug() {
+        let input =
+            fs::read_to_string("./resources/input9").expect("Could not load the input file");
+        let (_, tiles) = tiles(&input).expect("Could not parse the input data");
+        let polygon = Polygon::new(tiles).expect("Could not build the polygon");
+
+        let space = get_compression(&polygon);
+        let compressed = compress(&polygon, &space);
+        let corner = |x, y| {
+            let [x, y] = space.compress(&[x, y]).expect("not a red tile");
+            Point::new(x as u64, y as u64)
+        };
+        let t1 = corner(11937, 77901);
+        let t2 = corner(11204, 77297);
+
+        let sums = outside_prefix_sums(&compressed.rasterize());
+        assert_eq!(outside_tiles(&sums, t1, t2), 0);
     }
 }
@@ -0,0 +1,223 @@
+use std::fmt::Display;
+use std::io;
+
+use nom::character::complete::multispace0;
+use nom::combinator::all_consuming;
+use nom::error::ErrorKind;
+use nom::sequence::terminated;
+use nom::{Offset, Parser};
+
+/// Errors raised while loading, parsing or solving a puzzle.
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    /// The input does not follow the expected format, positions start at 1
+    Parse {
+        line: usize,
+        column: usize,
+        message: String,
+        /// Text of the offending line
+        text: String,
+    },
+    /// The input is well formed but cannot be solved as is
+    InvalidInput(String),
+    NoSolution(String),
+    /// Two sequences that must be compared element wise have different lengths
+    LengthMismatch {
+        expected: usize,
+        actual: usize,
+    },
+    /// The puzzle site could not be reached or answered with an error
+    Http(String),
+    /// A setting such as the session token is missing or invalid
+    Config(String),
+    /// The answer was not sent, the history tells it cannot be right or is not needed
+    SubmissionRefused(String),
+    /// A day could not be built or did not report its answers
+    Build(String),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Longest part of the offending line shown in a parse error.
+const SNIPPET_WIDTH: usize = 60;
+
+/// Runs a parser that must consume the whole input, only trailing whitespace may be left.
+pub fn parse_all<'a, O, P>(input: &'a str, parser: P) -> Result<O>
+where
+    P: Parser<&'a str, Output = O, Error = nom::error::Error<&'a str>>,
+{
+    all_consuming(terminated(parser, multispace0))
+        .parse(input)
+        .map(|(_, output)| output)
+        .map_err(|e| Error::parse(input, e))
+}
+
+impl Error {
+    /// Locates a nom error in the input it was raised on.
+    pub fn parse(input: &str, err: nom::Err<nom::error::Error<&str>>) -> Self {
+        let (offset, message) = match err {
+            nom::Err::Incomplete(_) => (input.len(), "unexpected end of input".to_string()),
+            // the parsers stopped before the remainder, point at its first character
+            nom::Err::Error(e) | nom::Err::Failure(e) if e.code == ErrorKind::Eof => (
+                offset_in(input, e.input.trim_start()),
+                "unexpected trailing input".to_string(),
+            ),
+            nom::Err::Error(e) | nom::Err::Failure(e) => (
+                offset_in(input, e.input),
+                format!("unexpected input ({})", e.code.description()),
+            ),
+        };
+        Error::at_offset(input, offset, message)
+    }
+
+    /// Builds a parse error at the given byte offset of the input.
+    pub fn at_offset(input: &str, offset: usize, message: String) -> Self {
+        let offset = offset.min(input.len());
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |i| offset + i);
+        Error::Parse {
+            line: before.matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            message,
+            text: input[line_start..line_end]
+                .trim_end_matches('\r')
+                .to_string(),
+        }
+    }
+}
+
+/// Offset of a sub slice in the input, the end of the input if it is not part of it.
+fn offset_in(input: &str, slice: &str) -> usize {
+    let start = input.as_ptr() as usize;
+    let position = slice.as_ptr() as usize;
+    if position >= start && position <= start + input.len() {
+        input.offset(slice)
+    } else {
+        input.len()
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Io(e) => write!(f, "{e}"),
+            Error::Parse {
+                line,
+                column,
+                message,
+                text,
+            } => write!(
+                f,
+                "parse error at line {line}, column {column} : {message} in `{}`",
+                snippet(text, *column)
+            ),
+            Error::InvalidInput(message) => write!(f, "invalid input : {message}"),
+            Error::NoSolution(message) => write!(f, "no solution : {message}"),
+            Error::LengthMismatch { expected, actual } => {
+                write!(f, "length mismatch : expected {expected}, got {actual}")
+            }
+            Error::Http(message) => write!(f, "http error : {message}"),
+            Error::Config(message) => write!(f, "configuration error : {message}"),
+            Error::SubmissionRefused(message) => write!(f, "submission refused : {message}"),
+            Error::Build(message) => write!(f, "build error : {message}"),
+        }
+    }
+}
+
+/// Part of a line around a 1-based column, long lines being cut with ellipses.
+fn snippet(text: &str, column: usize) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    if chars.len() <= SNIPPET_WIDTH {
+        return text.to_string();
+    }
+    let start = column
+        .saturating_sub(1)
+        .saturating_sub(SNIPPET_WIDTH / 2)
+        .min(chars.len() - SNIPPET_WIDTH);
+    let end = start + SNIPPET_WIDTH;
+    format!(
+        "{}{}{}",
+        if start > 0 { "..." } else { "" },
+        chars[start..end].iter().collect::<String>(),
+        if end < chars.len() { "..." } else { "" }
+    )
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(value: io::Error) -> Self {
+        Error::Io(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use nom::Parser;
+    use nom::character::complete::{char, u64};
+    use nom::multi::separated_list1;
+
+    use super::{Error, SNIPPET_WIDTH, parse_all, snippet};
+
+    #[test]
+    fn parse_position() {
+        let input = "12\n34\n5x6";
+        let err = (u64, char('\n'), u64, char('\n'), u64, char('\n'))
+            .parse(input)
+            .expect_err("the input is not valid");
+        match Error::parse(input, err) {
+            Error::Parse {
+                line, column, text, ..
+            } => assert_eq!((line, column, text.as_str()), (3, 2, "5x6")),
+            e => panic!("unexpected error {e}"),
+        }
+
+        match Error::at_offset(input, 0, String::new()) {
+            Error::Parse { line, column, .. } => assert_eq!((line, column), (1, 1)),
+            e => panic!("unexpected error {e}"),
+        }
+    }
+
+    #[test]
+    fn whole_input() {
+        let numbers = || separated_list1(char('\n'), u64::<_, _>);
+        assert_eq!(
+            parse_all("12\n34\n", numbers()).expect("trailing whitespace is allowed"),
+            vec![12, 34]
+        );
+
+        let err = parse_all("12\n34\nx5\n6", numbers()).expect_err("the input is not valid");
+        assert_eq!(
+            err.to_string(),
+            "parse error at line 3, column 1 : unexpected trailing input in `x5`"
+        );
+    }
+
+    #[test]
+    fn long_line() {
+        let text = format!("{}x{}", "a".repeat(50), "b".repeat(69));
+        assert!(text.len() > SNIPPET_WIDTH);
+        // the column of `x` is 51, the snippet is centered on it
+        assert_eq!(
+            snippet(&text, 51),
+            format!("...{}x{}...", "a".repeat(30), "b".repeat(29))
+        );
+        assert_eq!(
+            snippet(&text, 1),
+            format!("{}x{}...", "a".repeat(50), "b".repeat(9))
+        );
+        assert_eq!(snippet(&text, 120), format!("...{}", &text[60..]));
+    }
+}
@@ -0,0 +1,327 @@
+use crate::solution::{Answer, Answers, Part};
+
+/// Sample input of a puzzle statement with the answers given for it.
+#[derive(Debug)]
+pub struct Example {
+    pub day: u8,
+    pub input: &'static str,
+    /// Parts without an answer are not run on the example
+    pub expected: Answers,
+}
+
+impl Example {
+    /// Requested parts having an expected answer.
+    pub fn parts(&self, requested: Part) -> Option<Part> {
+        let part1 = self.expected.part1.is_some() && requested.includes_part1();
+        let part2 = self.expected.part2.is_some() && requested.includes_part2();
+        match (part1, part2) {
+            (true, true) => Some(Part::Both),
+            (true, false) => Some(Part::One),
+            (false, true) => Some(Part::Two),
+            (false, false) => None,
+        }
+    }
+}
+
+/// Examples of the given day, in the order of the puzzle statement.
+pub fn examples(day: u8) -> impl Iterator<Item = &'static Example> {
+    EXAMPLES.iter().filter(move |e| e.day == day)
+}
+
+pub const DAY1: &str = "L68
+L30
+R48
+L5
+R60
+L55
+L1
+L99
+R14
+L82";
+
+/// Rotations of several full turns
+pub const DAY1_FULL_TURNS: &str = "R1000\nL1000";
+
+pub const DAY2: &str = "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,1698522-1698528,446443-446449,38593856-38593862,565653-565659,824824821-824824827,2121212118-2121212124";
+
+pub const DAY3: &str = "987654321111111
+811111111111119
+234234234234278
+818181911112111";
+
+pub const DAY4: &str = "..@@.@@@@.
+@@@.@.@.@@
+@@@@@.@.@@
+@.@@@@..@.
+@@.@@@@.@@
+.@@@@@@@.@
+.@.@.@.@@@
+@.@@@.@@@@
+.@@@@@@@@.
+@.@.@@@.@.";
+
+pub const DAY5: &str = "3-5
+10-14
+16-20
+12-18
+9-21
+
+1
+5
+8
+11
+17
+32";
+
+pub const DAY6: &str = "123 328  51 64 
+ 45 64  387 23 
+  6 98  215 314
+*   +   *   +  ";
+
+pub const DAY7: &str = ".......S.......
+...............
+.......^.......
+...............
+......^.^......
+...............
+.....^.^.^.....
+...............
+....^.^...^....
+...............
+...^.^...^.^...
+...............
+..^...^.....^..
+...............
+.^.^.^.^.^...^.
+...............";
+
+pub const DAY8: &str = "162,817,812
+57,618,57
+906,360,560
+592,479,940
+352,342,300
+466,668,158
+542,29,236
+431,825,988
+739,650,466
+52,470,668
+216,146,977
+819,987,18
+117,168,530
+805,96,715
+346,949,466
+970,615,88
+941,993,340
+862,61,35
+984,92,344
+425,690,689";
+
+pub const DAY9: &str = "7,1
+11,1
+11,7
+9,7
+9,5
+2,5
+2,3
+7,3";
+
+pub const DAY10: &str = "[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}
+[...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}
+[.###.#] (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}";
+
+/// Paths starting from `you`
+pub const DAY11_YOU: &str = "aaa: you hhh
+you: bbb ccc
+bbb: ddd eee
+ccc: ddd eee fff
+ddd: ggg
+eee: out
+fff: out
+ggg: out
+hhh: ccc fff iii
+iii: out";
+
+/// Paths starting from `svr` through `dac` and `fft`
+pub const DAY11_SVR: &str = "svr: aaa bbb
+aaa: fft
+fft: ccc
+bbb: tty
+tty: ccc
+ccc: ddd eee
+ddd: hub
+hub: fff
+eee: dac
+dac: fff
+fff: ggg hhh
+ggg: out
+hhh: out";
+
+pub const DAY12: &str = "0:
+###
+##.
+##.
+
+1:
+###
+##.
+.##
+
+2:
+.##
+###
+##.
+
+3:
+##.
+###
+##.
+
+4:
+###
+#..
+###
+
+5:
+###
+.#.
+###
+
+4x4: 0 0 0 0 2 0
+12x5: 1 0 1 0 2 2
+12x5: 1 0 1 0 3 2";
+
+pub static EXAMPLES: [Example; 14] = [
+    Example {
+        day: 1,
+        input: DAY1,
+        expected: Answers {
+            part1: Some(Answer::Unsigned(3)),
+            part2: Some(Answer::Unsigned(6)),
+        },
+    },
+    Example {
+        day: 1,
+        input: DAY1_FULL_TURNS,
+        expected: Answers {
+            part1: None,
+            part2: Some(Answer::Unsigned(20)),
+        },
+    },
+    Example {
+        day: 2,
+        input: DAY2,
+        expected: Answers {
+            part1: Some(Answer::Unsigned(1227775554)),
+            part2: Some(Answer::Unsigned(4174379265)),
+        },
+    },
+    Example {
+        day: 3,
+        input: DAY3,
+        expected: Answers {
+            part1: Some(Answer::Unsigned(357)),
+            part2: Some(Answer::Unsigned(3121910778619)),
+        },
+    },
+    Example {
+        day: 4,
+        input: DAY4,
+        expected: Answers {
+            part1: Some(Answer::Unsigned(13)),
+            part2: Some(Answer::Unsigned(43)),
+        },
+    },
+    Example {
+        day: 5,
+        input: DAY5,
+        expected: Answers {
+            part1: Some(Answer::Unsigned(3)),
+            part2: Some(Answer::Unsigned(16)),
+        },
+    },
+    Example {
+        day: 6,
+        input: DAY6,
+        expected: Answers {
+            part1: Some(Answer::Unsigned(4277556)),
+            part2: Some(Answer::Unsigned(3263827)),
+        },
+    },
+    Example {
+        day: 7,
+        input: DAY7,
+        expected: Answers {
+            part1: Some(Answer::Unsigned(21)),
+            part2: Some(Answer::Unsigned(40)),
+        },
+    },
+    // part 1 of the example joins 10 pairs of boxes instead of 1000
+    Example {
+        day: 8,
+        input: DAY8,
+        expected: Answers {
+            part1: None,
+            part2: Some(Answer::Unsigned(25272)),
+        },
+    },
+    Example {
+        day: 9,
+        input: DAY9,
+        expected: Answers {
+            part1: Some(Answer::Unsigned(50)),
+            part2: Some(Answer::Unsigned(24)),
+        },
+    },
+    Example {
+        day: 10,
+        input: DAY10,
+        expected: Answers {
+            part1: Some(Answer::Unsigned(7)),
+            part2: Some(Answer::Unsigned(33)),
+        },
+    },
+    Example {
+        day: 11,
+        input: DAY11_YOU,
+        expected: Answers {
+            part1: Some(Answer::Unsigned(5)),
+            part2: None,
+        },
+    },
+    Example {
+        day: 11,
+        input: DAY11_SVR,
+        expected: Answers {
+            part1: None,
+            part2: Some(Answer::Unsigned(2)),
+        },
+    },
+    Example {
+        day: 12,
+        input: DAY12,
+        expected: Answers {
+            part1: Some(Answer::Unsigned(2)),
+            part2: None,
+        },
+    },
+];
+
+#[cfg(test)]
+mod tests {
+
+    use crate::registry;
+    use crate::solution::Part;
+
+    use super::{EXAMPLES, examples};
+
+    #[test]
+    fn registry() {
+        assert_eq!(examples(11).count(), 2);
+        assert_eq!(examples(13).count(), 0);
+        assert!(EXAMPLES.iter().all(|e| registry::solution(e.day).is_some()));
+        assert!(EXAMPLES.iter().all(|e| e.parts(Part::Both).is_some()));
+        let day8 = examples(8).next().expect("day 8 has an example");
+        assert_eq!(day8.parts(Part::Both), Some(Part::Two));
+        assert_eq!(day8.parts(Part::One), None);
+    }
+}
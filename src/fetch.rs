@@ -0,0 +1,83 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::{Error, Result};
+use crate::http::{HttpClient, SiteConfig};
+use crate::input;
+
+/// How the input of a day was made available.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fetched {
+    /// The input was already on disk, nothing was requested
+    Cached(PathBuf),
+    Downloaded(PathBuf),
+}
+
+pub fn input_url(config: &SiteConfig, day: u8) -> String {
+    format!("{}/input", config.day_url(day))
+}
+
+/// Downloads the input of the day to `inputN` in the resources directory, unless it is
+/// already there.
+pub fn fetch_input(
+    client: &dyn HttpClient,
+    config: &SiteConfig,
+    resources_dir: &Path,
+    day: u8,
+) -> Result<Fetched> {
+    let path = input::input_path(resources_dir, day);
+    if path.exists() {
+        return Ok(Fetched::Cached(path));
+    }
+
+    let input = client.get(&input_url(config, day), config.session()?)?;
+    if input.trim().is_empty() {
+        return Err(Error::Http(format!("the input of day {day} is empty")));
+    }
+    fs::create_dir_all(resources_dir)?;
+    // written aside then renamed so that an interrupted download is never taken as cached
+    let partial = path.with_extension("part");
+    fs::write(&partial, &input)?;
+    fs::rename(&partial, &path)?;
+    Ok(Fetched::Downloaded(path))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::fs;
+
+    use crate::error::Error;
+    use crate::http::SiteConfig;
+    use crate::testing::{StubSite, TempDir};
+
+    use super::{Fetched, fetch_input};
+
+    #[test]
+    fn cache() {
+        let dir = TempDir::new("fetch");
+        let site = StubSite::replying("L68\nL30\n");
+        let config = StubSite::config();
+
+        let first = fetch_input(&site, &config, dir.path(), 1).expect("Could not fetch the input");
+        let path = dir.path().join("input1");
+        assert_eq!(first, Fetched::Downloaded(path.clone()));
+        assert_eq!(
+            fs::read_to_string(&path).expect("no cached input"),
+            "L68\nL30\n"
+        );
+
+        // the session is not needed any more once cached
+        let config = SiteConfig {
+            session: None,
+            ..config
+        };
+        let second = fetch_input(&site, &config, dir.path(), 1).expect("Could not fetch the input");
+        assert_eq!(second, Fetched::Cached(path));
+        assert_eq!(site.urls(), vec!["http://localhost:8080/day/1/input"]);
+        assert!(matches!(
+            fetch_input(&site, &config, dir.path(), 2),
+            Err(Error::Config(_))
+        ));
+    }
+}
@@ -0,0 +1,361 @@
+use std::fmt::Display;
+
+use itertools::Itertools;
+
+use crate::error::{Error, Result};
+use crate::grid::Grid;
+
+/// Point of the plane with integer coordinates, `y` growing downwards as in the puzzles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point {
+    pub x: u64,
+    pub y: u64,
+}
+
+impl Point {
+    pub fn new(x: u64, y: u64) -> Self {
+        Point { x, y }
+    }
+
+    /// Number of tiles of the rectangle having both points as opposite corners.
+    pub fn rectangle_area(&self, other: &Self) -> u64 {
+        (self.x.abs_diff(other.x) + 1) * (self.y.abs_diff(other.y) + 1)
+    }
+}
+
+impl Display for Point {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+/// Whether the point is on the segment, both being axis aligned.
+fn on_segment(point: Point, (a, b): (Point, Point)) -> bool {
+    (a.x.min(b.x)..=a.x.max(b.x)).contains(&point.x)
+        && (a.y.min(b.y)..=a.y.max(b.y)).contains(&point.y)
+}
+
+/// Common part of two axis aligned segments, as its two corners.
+fn overlap((a, b): (Point, Point), (c, d): (Point, Point)) -> Option<(Point, Point)> {
+    let low = Point::new(
+        a.x.min(b.x).max(c.x.min(d.x)),
+        a.y.min(b.y).max(c.y.min(d.y)),
+    );
+    let high = Point::new(
+        a.x.max(b.x).min(c.x.max(d.x)),
+        a.y.max(b.y).min(c.y.max(d.y)),
+    );
+    (low.x <= high.x && low.y <= high.y).then_some((low, high))
+}
+
+/// Closed polygon whose edges are all horizontal or vertical and never cross.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polygon {
+    vertices: Vec<Point>,
+}
+
+impl Polygon {
+    /// Checks that the vertices, the last one being linked back to the first one, make
+    /// an orthogonal polygon that does not intersect itself.
+    pub fn new(vertices: Vec<Point>) -> Result<Self> {
+        if vertices.len() < 4 {
+            return Err(Error::InvalidInput(format!(
+                "a polygon needs at least 4 vertices, got {}",
+                vertices.len()
+            )));
+        }
+        let polygon = Polygon { vertices };
+        let edges: Vec<_> = polygon.edges().collect();
+        if let Some((a, b)) = edges
+            .iter()
+            .find(|(a, b)| a == b || (a.x != b.x && a.y != b.y))
+        {
+            return Err(Error::InvalidInput(format!(
+                "the edge {a} - {b} is not horizontal nor vertical"
+            )));
+        }
+
+        let n = edges.len();
+        for i in 0..n {
+            for j in i + 1..n {
+                let Some(common) = overlap(edges[i], edges[j]) else {
+                    continue;
+                };
+                // consecutive edges only share their common vertex
+                let shared = if j == i + 1 {
+                    Some(edges[i].1)
+                } else if i == 0 && j == n - 1 {
+                    Some(edges[i].0)
+                } else {
+                    None
+                };
+                if shared.is_none_or(|v| common != (v, v)) {
+                    return Err(Error::InvalidInput(format!(
+                        "the edges {} - {} and {} - {} intersect",
+                        edges[i].0, edges[i].1, edges[j].0, edges[j].1
+                    )));
+                }
+            }
+        }
+        Ok(polygon)
+    }
+
+    pub fn vertices(&self) -> &[Point] {
+        &self.vertices
+    }
+
+    /// Edges as pairs of consecutive vertices, the closing edge included.
+    pub fn edges(&self) -> impl Iterator<Item = (Point, Point)> + '_ {
+        self.vertices
+            .iter()
+            .zip(self.vertices.iter().cycle().skip(1))
+            .map(|(a, b)| (*a, *b))
+    }
+
+    pub fn on_edge(&self, point: Point) -> bool {
+        self.edges().any(|edge| on_segment(point, edge))
+    }
+
+    /// Whether the point is inside the polygon or on its border.
+    pub fn contains(&self, point: Point) -> bool {
+        if self.on_edge(point) {
+            return true;
+        }
+        // A ray cast towards growing `x` crosses the border at vertical edges only. Each
+        // edge holds its lower end but not its upper one, so a ray going through a vertex
+        // or along a horizontal edge counts a crossing only when the border really goes
+        // from one side of the ray to the other.
+        self.edges()
+            .filter(|(a, b)| {
+                a.x == b.x && a.x > point.x && (a.y.min(b.y)..a.y.max(b.y)).contains(&point.y)
+            })
+            .count()
+            % 2
+            == 1
+    }
+
+    /// Tiles from `0,0` to the farthest vertex, true inside the polygon or on its border,
+    /// as rows of growing `y`. Much faster than asking [`Polygon::contains`] for every tile,
+    /// as each row is swept once.
+    ///
+    /// One cell is allocated per tile, so it is meant for compressed or small polygons
+    /// only.
+    pub(crate) fn rasterize(&self) -> Grid<bool> {
+        let width = self.vertices.iter().map(|v| v.x).max().unwrap_or_default() as usize + 1;
+        let height = self.vertices.iter().map(|v| v.y).max().unwrap_or_default() as usize + 1;
+        let mut rows = vec![vec![false; width]; height];
+        for (a, b) in self.edges() {
+            for row in &mut rows[a.y.min(b.y) as usize..=a.y.max(b.y) as usize] {
+                row[a.x.min(b.x) as usize..=a.x.max(b.x) as usize].fill(true);
+            }
+        }
+        for (y, row) in rows.iter_mut().enumerate() {
+            // the edges crossed by the row, with the same rule as `contains`
+            let crossings: Vec<_> = self
+                .edges()
+                .filter(|(a, b)| a.x == b.x && (a.y.min(b.y)..a.y.max(b.y)).contains(&(y as u64)))
+                .map(|(a, _)| a.x as usize)
+                .sorted_unstable()
+                .collect();
+            for pair in crossings.chunks_exact(2) {
+                row[pair[0]..=pair[1]].fill(true);
+            }
+        }
+        Grid::from_rows(rows).expect("the rows have the same width")
+    }
+
+    /// Length of the border.
+    pub fn perimeter(&self) -> u64 {
+        self.edges()
+            .map(|(a, b)| a.x.abs_diff(b.x) + a.y.abs_diff(b.y))
+            .sum()
+    }
+
+    /// Area enclosed by the edges, the vertices being points without size.
+    pub fn area(&self) -> u64 {
+        // shoelace formula, the sign depending on the orientation
+        let twice: i128 = self
+            .edges()
+            .map(|(a, b)| a.x as i128 * b.y as i128 - b.x as i128 * a.y as i128)
+            .sum();
+        (twice.unsigned_abs() / 2) as u64
+    }
+
+    /// Number of tiles inside the polygon or on its border, the vertices being tiles.
+    pub fn tile_count(&self) -> u64 {
+        // Pick's theorem, every tile of the border being a point of the grid on it
+        self.area() + self.perimeter() / 2 + 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::error::Error;
+    use crate::examples::DAY9;
+
+    use super::{Point, Polygon};
+
+    fn polygon(text: &str) -> Polygon {
+        let vertices = text
+            .lines()
+            .map(|l| {
+                let (x, y) = l.split_once(',').expect("no comma");
+                Point::new(
+                    x.parse().expect("no x value"),
+                    y.parse().expect("no y value"),
+                )
+            })
+            .collect();
+        Polygon::new(vertices).expect("Could not build the polygon")
+    }
+
+    #[test]
+    fn validation() {
+        let points = |coordinates: &[(u64, u64)]| {
+            coordinates
+                .iter()
+                .map(|(x, y)| Point::new(*x, *y))
+                .collect::<Vec<_>>()
+        };
+        assert!(Polygon::new(points(&[(0, 0), (2, 0), (2, 2), (0, 2)])).is_ok());
+        // not closed by an axis aligned edge
+        assert!(Polygon::new(points(&[(0, 0), (2, 0), (2, 2), (1, 3)])).is_err());
+        assert!(Polygon::new(points(&[(0, 0), (2, 0), (2, 2)])).is_err());
+        // a bow tie
+        assert!(matches!(
+            Polygon::new(points(&[(0, 0), (2, 0), (2, 2), (4, 2), (4, 1), (0, 1)])),
+            Err(Error::InvalidInput(_))
+        ));
+        // going back on its steps
+        assert!(Polygon::new(points(&[(0, 0), (3, 0), (1, 0), (1, 2), (0, 2)])).is_err());
+    }
+
+    #[test]
+    fn inside() {
+        let polygon = polygon(DAY9);
+        assert!(polygon.on_edge(Point::new(9, 6)));
+        assert!(!polygon.on_edge(Point::new(8, 4)));
+        assert!(polygon.contains(Point::new(8, 4)));
+        assert!(polygon.contains(Point::new(11, 1)));
+        assert!(!polygon.contains(Point::new(3, 2)));
+        assert!(!polygon.contains(Point::new(10, 8)));
+        // rays along horizontal edges and through vertices
+        assert!(!polygon.contains(Point::new(1, 5)));
+        assert!(!polygon.contains(Point::new(1, 3)));
+        assert!(polygon.contains(Point::new(8, 5)));
+        assert!(!polygon.contains(Point::new(6, 1)));
+        assert!(!polygon.contains(Point::new(8, 7)));
+    }
+
+    /// Numbers for the generated polygons, the same on every run.
+    struct XorShift(u64);
+
+    impl XorShift {
+        fn below(&mut self, bound: u64) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0 % bound
+        }
+    }
+
+    /// Random orthogonal polygon in `0..size`, alternating horizontal and vertical edges.
+    fn random_polygon(rng: &mut XorShift, size: u64) -> Polygon {
+        loop {
+            let k = 2 + rng.below(4) as usize;
+            let xs: Vec<_> = (0..k).map(|_| rng.below(size)).collect();
+            let ys: Vec<_> = (0..k).map(|_| rng.below(size)).collect();
+            let vertices = (0..k)
+                .flat_map(|i| [Point::new(xs[i], ys[i]), Point::new(xs[(i + 1) % k], ys[i])])
+                .collect();
+            if let Ok(polygon) = Polygon::new(vertices) {
+                return polygon;
+            }
+        }
+    }
+
+    /// Tiles inside the polygon or on its border, found by drawing the border on a grid
+    /// of twice the resolution and flooding it from the outside. The doubled grid keeps
+    /// a free line between two edges one tile apart.
+    fn rasterize(polygon: &Polygon, size: u64) -> Vec<Vec<bool>> {
+        // one free line on each side so that the outside is connected
+        let side = 2 * size as usize + 1;
+        let mut border = vec![vec![false; side + 2]; side + 2];
+        for (a, b) in polygon.edges() {
+            for x in 2 * a.x.min(b.x)..=2 * a.x.max(b.x) {
+                for y in 2 * a.y.min(b.y)..=2 * a.y.max(b.y) {
+                    border[x as usize + 1][y as usize + 1] = true;
+                }
+            }
+        }
+        let mut outside = vec![vec![false; side + 2]; side + 2];
+        let mut stack = vec![(0, 0)];
+        while let Some((x, y)) = stack.pop() {
+            if border[x][y] || outside[x][y] {
+                continue;
+            }
+            outside[x][y] = true;
+            if x > 0 {
+                stack.push((x - 1, y));
+            }
+            if y > 0 {
+                stack.push((x, y - 1));
+            }
+            if x + 1 < side + 2 {
+                stack.push((x + 1, y));
+            }
+            if y + 1 < side + 2 {
+                stack.push((x, y + 1));
+            }
+        }
+        (0..size as usize)
+            .map(|x| {
+                (0..size as usize)
+                    .map(|y| !outside[2 * x + 1][2 * y + 1])
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn against_rasterization() {
+        let mut rng = XorShift(0x2025_0009);
+        let size = 12;
+        for _ in 0..500 {
+            let polygon = random_polygon(&mut rng, size);
+            let raster = rasterize(&polygon, size);
+            let swept = polygon.rasterize();
+            for x in 0..size {
+                for y in 0..size {
+                    assert_eq!(
+                        polygon.contains(Point::new(x, y)),
+                        raster[x as usize][y as usize],
+                        "tile {x},{y} of {:?}",
+                        polygon.vertices()
+                    );
+                    assert_eq!(
+                        swept
+                            .get((y as usize, x as usize))
+                            .copied()
+                            .unwrap_or(false),
+                        raster[x as usize][y as usize],
+                        "swept tile {x},{y} of {:?}",
+                        polygon.vertices()
+                    );
+                }
+            }
+            let tiles = raster.iter().flatten().filter(|t| **t).count() as u64;
+            assert_eq!(polygon.tile_count(), tiles, "{:?}", polygon.vertices());
+        }
+    }
+
+    #[test]
+    fn area() {
+        let polygon = polygon(DAY9);
+        assert_eq!(polygon.perimeter(), 30);
+        assert_eq!(polygon.area(), 30);
+        assert_eq!(polygon.tile_count(), 46);
+        assert_eq!(Point::new(2, 5).rectangle_area(&Point::new(11, 1)), 50);
+    }
+}
@@ -0,0 +1,211 @@
+use std::collections::HashMap;
+
+use crate::error::{Error, Result};
+
+/// Index of a node in its graph.
+pub type NodeId = usize;
+
+/// Directed graph whose nodes are interned names.
+#[derive(Debug, Clone, Default)]
+pub struct Graph {
+    names: Vec<String>,
+    ids: HashMap<String, NodeId>,
+    successors: Vec<Vec<NodeId>>,
+}
+
+/// Progress of the depth first search on a node.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Visit {
+    New,
+    /// On the current path, meeting it again closes a cycle
+    InProgress,
+    Done,
+}
+
+impl Graph {
+    pub fn new() -> Self {
+        Graph::default()
+    }
+
+    /// Id of the node, added if the name is new.
+    pub fn node(&mut self, name: &str) -> NodeId {
+        if let Some(id) = self.ids.get(name) {
+            return *id;
+        }
+        let id = self.names.len();
+        self.names.push(name.to_string());
+        self.ids.insert(name.to_string(), id);
+        self.successors.push(Vec::new());
+        id
+    }
+
+    pub fn id(&self, name: &str) -> Option<NodeId> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn name(&self, id: NodeId) -> &str {
+        &self.names[id]
+    }
+
+    /// Adds the edge, and its nodes if they are new.
+    pub fn add_edge(&mut self, from: &str, to: &str) {
+        let (from, to) = (self.node(from), self.node(to));
+        self.successors[from].push(to);
+    }
+
+    pub fn successors(&self, id: NodeId) -> &[NodeId] {
+        &self.successors[id]
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    fn known(&self, name: &str) -> Result<NodeId> {
+        self.id(name)
+            .ok_or_else(|| Error::InvalidInput(format!("no node is named {name}")))
+    }
+
+    /// Nodes reachable from the starts, each one after all its successors, or an error
+    /// naming a cycle met on the way.
+    fn postorder(&self, starts: impl IntoIterator<Item = NodeId>) -> Result<Vec<NodeId>> {
+        let mut visits = vec![Visit::New; self.len()];
+        let mut order = Vec::new();
+        for start in starts {
+            if visits[start] != Visit::New {
+                continue;
+            }
+            // the nodes of the current path with the index of their next successor
+            let mut stack = vec![(start, 0)];
+            visits[start] = Visit::InProgress;
+            while let Some((node, next)) = stack.last_mut() {
+                match self.successors[*node].get(*next) {
+                    Some(&successor) => {
+                        *next += 1;
+                        match visits[successor] {
+                            Visit::New => {
+                                visits[successor] = Visit::InProgress;
+                                stack.push((successor, 0));
+                            }
+                            Visit::InProgress => {
+                                let from = stack.iter().position(|(n, _)| *n == successor);
+                                let cycle: Vec<_> = stack[from.unwrap_or_default()..]
+                                    .iter()
+                                    .chain([&(successor, 0)])
+                                    .map(|(n, _)| self.name(*n))
+                                    .collect();
+                                return Err(Error::InvalidInput(format!(
+                                    "the graph has a cycle {}",
+                                    cycle.join(" -> ")
+                                )));
+                            }
+                            Visit::Done => {}
+                        }
+                    }
+                    None => {
+                        visits[*node] = Visit::Done;
+                        order.push(*node);
+                        stack.pop();
+                    }
+                }
+            }
+        }
+        Ok(order)
+    }
+
+    /// Every node before its successors, or an error if the graph has a cycle.
+    pub fn topological_order(&self) -> Result<Vec<NodeId>> {
+        let mut order = self.postorder(0..self.len())?;
+        order.reverse();
+        Ok(order)
+    }
+
+    pub fn has_cycle(&self) -> bool {
+        self.topological_order().is_err()
+    }
+
+    /// Whether each node can be reached from `from`, itself included.
+    pub fn reachable(&self, from: NodeId) -> Vec<bool> {
+        let mut reached = vec![false; self.len()];
+        let mut stack = vec![from];
+        reached[from] = true;
+        while let Some(node) = stack.pop() {
+            for &successor in &self.successors[node] {
+                if !reached[successor] {
+                    reached[successor] = true;
+                    stack.push(successor);
+                }
+            }
+        }
+        reached
+    }
+
+    /// Number of paths from `from` to `to`, computed once per node in O(V+E) without
+    /// listing them. The nodes reachable from `from` must not form a cycle.
+    pub fn count_paths(&self, from: &str, to: &str) -> Result<u64> {
+        let (from, to) = (self.known(from)?, self.known(to)?);
+        let mut paths = vec![0u64; self.len()];
+        // the successors of a node are counted before it
+        for node in self.postorder([from])? {
+            paths[node] = if node == to {
+                1
+            } else {
+                self.successors[node].iter().map(|s| paths[*s]).sum()
+            };
+        }
+        Ok(paths[from])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::error::Error;
+
+    use super::Graph;
+
+    fn graph(edges: &[(&str, &str)]) -> Graph {
+        let mut graph = Graph::new();
+        edges.iter().for_each(|(from, to)| graph.add_edge(from, to));
+        graph
+    }
+
+    #[test]
+    fn paths() {
+        let graph = graph(&[("a", "b"), ("a", "c"), ("b", "d"), ("c", "d"), ("d", "e")]);
+        assert_eq!(graph.len(), 5);
+        assert_eq!(graph.count_paths("a", "e").expect("Could not count"), 2);
+        assert_eq!(graph.count_paths("b", "e").expect("Could not count"), 1);
+        assert_eq!(graph.count_paths("e", "a").expect("Could not count"), 0);
+        assert!(matches!(
+            graph.count_paths("a", "z"),
+            Err(Error::InvalidInput(_))
+        ));
+
+        let order = graph.topological_order().expect("Could not sort");
+        let rank = |name| order.iter().position(|n| graph.name(*n) == name);
+        assert!(rank("a") < rank("b") && rank("b") < rank("d") && rank("c") < rank("d"));
+        assert_eq!(
+            graph.reachable(graph.id("b").expect("no b")),
+            vec![false, true, false, true, true]
+        );
+    }
+
+    #[test]
+    fn cycles() {
+        let graph = graph(&[("a", "b"), ("b", "c"), ("c", "b"), ("x", "y")]);
+        assert!(graph.has_cycle());
+        match graph.count_paths("a", "c") {
+            Err(Error::InvalidInput(message)) => {
+                assert_eq!(message, "the graph has a cycle b -> c -> b")
+            }
+            other => panic!("unexpected result {other:?}"),
+        }
+        // the cycle is out of reach
+        assert_eq!(graph.count_paths("x", "y").expect("Could not count"), 1);
+    }
+}
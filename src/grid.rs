@@ -0,0 +1,259 @@
+use std::fmt::Display;
+use std::ops::{Index, IndexMut};
+
+use crate::error::{Error, Result};
+
+/// Position in a grid as (row, column), the origin being the top left cell.
+pub type Position = (usize, usize);
+
+/// Offsets of the four orthogonal neighbors.
+const ORTHOGONAL: [(isize, isize); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+
+/// Offsets of the eight neighbors, diagonals included.
+const ALL_AROUND: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// Rectangular 2D map stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Builds a grid from its rows, which must all have the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self> {
+        let width = rows.first().map_or(0, |r| r.len());
+        if let Some(i) = rows.iter().position(|r| r.len() != width) {
+            return Err(Error::InvalidInput(format!(
+                "row {} is {} wide instead of {width}",
+                i + 1,
+                rows[i].len()
+            )));
+        }
+        Ok(Grid {
+            width,
+            height: rows.len(),
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    /// Parses a character map, one row per line, `cell` rejecting the unknown characters.
+    pub fn parse(input: &str, mut cell: impl FnMut(char) -> Option<T>) -> Result<Self> {
+        let mut rows = Vec::new();
+        let mut offset = 0;
+        for line in input.lines() {
+            let mut row = Vec::new();
+            for (i, c) in line.char_indices() {
+                row.push(cell(c).ok_or_else(|| {
+                    Error::at_offset(input, offset + i, format!("unexpected character '{c}'"))
+                })?);
+            }
+            if let Some(first) = rows.first().map(Vec::len)
+                && row.len() != first
+            {
+                return Err(Error::at_offset(
+                    input,
+                    offset + line.len(),
+                    format!("the row is {} wide instead of {first}", row.len()),
+                ));
+            }
+            rows.push(row);
+            offset += line.len() + 1;
+        }
+        Grid::from_rows(rows)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (row, column): Position) -> bool {
+        row < self.height && column < self.width
+    }
+
+    pub fn get(&self, position: Position) -> Option<&T> {
+        self.contains(position)
+            .then(|| &self.cells[position.0 * self.width + position.1])
+    }
+
+    pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        self.contains(position)
+            .then(|| &mut self.cells[position.0 * self.width + position.1])
+    }
+
+    /// Position moved by the offset if it is still in the grid.
+    fn offset(&self, (row, column): Position, (dr, dc): (isize, isize)) -> Option<Position> {
+        let position = (row.checked_add_signed(dr)?, column.checked_add_signed(dc)?);
+        self.contains(position).then_some(position)
+    }
+
+    /// Positions of the up to 4 orthogonal neighbors in the grid.
+    pub fn neighbors4(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        ORTHOGONAL
+            .iter()
+            .filter_map(move |d| self.offset(position, *d))
+    }
+
+    /// Positions of the up to 8 neighbors in the grid, diagonals included.
+    pub fn neighbors8(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        ALL_AROUND
+            .iter()
+            .filter_map(move |d| self.offset(position, *d))
+    }
+
+    pub fn row(&self, row: usize) -> Option<&[T]> {
+        (row < self.height).then(|| &self.cells[row * self.width..(row + 1) * self.width])
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // a grid without columns still has its rows
+        (0..self.height).map(|r| &self.cells[r * self.width..(r + 1) * self.width])
+    }
+
+    pub fn column(&self, column: usize) -> impl Iterator<Item = &T> {
+        let width = self.width;
+        self.cells
+            .iter()
+            .skip(column)
+            .step_by(width.max(1))
+            .take(if column < width { self.height } else { 0 })
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|c| self.column(c))
+    }
+
+    /// Cells row by row.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    /// Cells row by row with their position.
+    pub fn enumerate(&self) -> impl Iterator<Item = (Position, &T)> {
+        let width = self.width;
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(i, cell)| ((i / width, i % width), cell))
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Position> + use<T> {
+        let width = self.width;
+        (0..self.height).flat_map(move |r| (0..width).map(move |c| (r, c)))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &T {
+        self.get(position).unwrap_or_else(|| {
+            panic!(
+                "position {position:?} out of a {}x{} grid",
+                self.height, self.width
+            )
+        })
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut T {
+        let (height, width) = (self.height, self.width);
+        self.get_mut(position)
+            .unwrap_or_else(|| panic!("position {position:?} out of a {height}x{width} grid"))
+    }
+}
+
+/// Renders the cells row by row, one line per row.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, row) in self.rows().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::error::Error;
+
+    use super::Grid;
+
+    fn digit(c: char) -> Option<u32> {
+        c.to_digit(10)
+    }
+
+    #[test]
+    fn access() {
+        let grid = Grid::parse("123\n456", digit).expect("Could not parse the grid");
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(1, 0)], 4);
+        assert_eq!(grid.get((2, 0)), None);
+        assert_eq!(grid.get((0, 3)), None);
+        assert_eq!(grid.row(1), Some(&[4, 5, 6][..]));
+        assert_eq!(grid.column(2).copied().collect::<Vec<_>>(), vec![3, 6]);
+        assert_eq!(grid.columns().count(), 3);
+        assert_eq!(grid.to_string(), "123\n456");
+        assert_eq!(grid.map(|d| d * 2).to_string(), "246\n81012");
+        assert_eq!(
+            grid.enumerate().find(|(_, d)| **d == 5).map(|(p, _)| p),
+            Some((1, 1))
+        );
+    }
+
+    #[test]
+    fn neighbors() {
+        let grid = Grid::parse("123\n456\n789", digit).expect("Could not parse the grid");
+        let mut around_corner: Vec<_> = grid.neighbors8((0, 0)).map(|p| grid[p]).collect();
+        around_corner.sort();
+        assert_eq!(around_corner, vec![2, 4, 5]);
+        assert_eq!(grid.neighbors8((1, 1)).count(), 8);
+        assert_eq!(
+            grid.neighbors4((1, 1)).map(|p| grid[p]).collect::<Vec<_>>(),
+            vec![2, 4, 6, 8]
+        );
+        assert_eq!(grid.neighbors4((2, 2)).count(), 2);
+    }
+
+    #[test]
+    fn invalid_maps() {
+        match Grid::parse("12\n3x", digit) {
+            Err(Error::Parse { line, column, .. }) => assert_eq!((line, column), (2, 2)),
+            other => panic!("unexpected result {other:?}"),
+        }
+        match Grid::parse("12\n345", digit) {
+            Err(Error::Parse { line, column, .. }) => assert_eq!((line, column), (2, 4)),
+            other => panic!("unexpected result {other:?}"),
+        }
+        assert!(Grid::from_rows(vec![vec![1], vec![]]).is_err());
+    }
+}
@@ -0,0 +1,128 @@
+use std::env;
+use std::fs;
+use std::path::Path;
+
+use ureq::Agent;
+use ureq::tls::{TlsConfig, TlsProvider};
+
+use crate::error::{Error, Result};
+
+/// Environment variable holding the session cookie of the puzzle site.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+/// Environment variable overriding the address of the puzzle site.
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+
+/// File of the resources directory holding the session cookie when the variable is not set.
+pub const SESSION_FILE: &str = "session";
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com/2025";
+
+/// The requests made to the puzzle site, a trait so that the transport can be swapped.
+pub trait HttpClient {
+    /// Body of a successful GET of `url`.
+    fn get(&self, url: &str, session: &str) -> Result<String>;
+
+    /// Body of a successful POST of the url encoded `form` to `url`.
+    fn post_form(&self, url: &str, session: &str, form: &[(&str, &str)]) -> Result<String>;
+}
+
+/// Where the puzzle site is and how to authenticate on it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SiteConfig {
+    pub base_url: String,
+    pub session: Option<String>,
+}
+
+impl SiteConfig {
+    /// Reads [`BASE_URL_VAR`] and the session from [`SESSION_VAR`] or else the
+    /// [`SESSION_FILE`] of the resources directory.
+    pub fn from_env(resources_dir: &Path) -> Self {
+        let base_url = env::var(BASE_URL_VAR).unwrap_or_else(|_| DEFAULT_BASE_URL.to_string());
+        let session = env::var(SESSION_VAR)
+            .ok()
+            .or_else(|| fs::read_to_string(resources_dir.join(SESSION_FILE)).ok())
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty());
+        SiteConfig {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session,
+        }
+    }
+
+    pub fn session(&self) -> Result<&str> {
+        self.session.as_deref().ok_or_else(|| {
+            Error::Config(format!(
+                "no session token, set {SESSION_VAR} or write it to the {SESSION_FILE} file of the resources directory"
+            ))
+        })
+    }
+
+    pub fn day_url(&self, day: u8) -> String {
+        format!("{}/day/{day}", self.base_url)
+    }
+}
+
+/// [`HttpClient`] backed by `ureq`, using the TLS library of the system.
+pub struct UreqClient {
+    agent: Agent,
+}
+
+impl Default for UreqClient {
+    fn default() -> Self {
+        let agent = Agent::config_builder()
+            .tls_config(
+                TlsConfig::builder()
+                    .provider(TlsProvider::NativeTls)
+                    .build(),
+            )
+            .http_status_as_error(false)
+            .user_agent(concat!(
+                env!("CARGO_PKG_NAME"),
+                "/",
+                env!("CARGO_PKG_VERSION")
+            ))
+            .build()
+            .into();
+        UreqClient { agent }
+    }
+}
+
+impl UreqClient {
+    fn body(url: &str, response: ureq::http::Response<ureq::Body>) -> Result<String> {
+        let status = response.status();
+        let body = response
+            .into_body()
+            .read_to_string()
+            .map_err(|e| Error::Http(format!("could not read the answer of {url} : {e}")))?;
+        if status.is_success() {
+            Ok(body)
+        } else {
+            // the first line is enough to tell what went wrong
+            let reason = body.trim().lines().next().unwrap_or_default();
+            Err(Error::Http(format!("{url} answered {status} : {reason}")))
+        }
+    }
+}
+
+impl HttpClient for UreqClient {
+    fn get(&self, url: &str, session: &str) -> Result<String> {
+        let response = self
+            .agent
+            .get(url)
+            .header("Cookie", &format!("session={session}"))
+            .call()
+            .map_err(|e| Error::Http(format!("could not get {url} : {e}")))?;
+        UreqClient::body(url, response)
+    }
+
+    fn post_form(&self, url: &str, session: &str, form: &[(&str, &str)]) -> Result<String> {
+        let response = self
+            .agent
+            .post(url)
+            .header("Cookie", &format!("session={session}"))
+            .send_form(form.iter().copied())
+            .map_err(|e| Error::Http(format!("could not post to {url} : {e}")))?;
+        UreqClient::body(url, response)
+    }
+}
@@ -0,0 +1,137 @@
+use std::env;
+use std::fmt::Display;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+/// Environment variable overriding the directory holding the `inputN` files.
+pub const RESOURCES_DIR_VAR: &str = "AOC2025_RESOURCES";
+
+const DEFAULT_RESOURCES_DIR: &str = "./resources";
+
+/// How much of the raw layout of an input a day depends on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Layout {
+    /// Whitespace at the end of the lines is meaningless
+    Free,
+    /// Columns are aligned with spaces, the lines keep their trailing whitespace
+    Aligned,
+}
+
+/// Where the puzzle input is read from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    /// `inputN` in the resources directory
+    Resources,
+    File(PathBuf),
+    Stdin,
+    Inline(String),
+}
+
+impl InputSource {
+    /// Builds the source from the `--input` argument, `-` meaning stdin.
+    pub fn from_arg(arg: Option<&str>) -> Self {
+        match arg {
+            None => InputSource::Resources,
+            Some("-") => InputSource::Stdin,
+            Some(path) => InputSource::File(PathBuf::from(path)),
+        }
+    }
+
+    pub fn read(&self, day: u8) -> io::Result<String> {
+        match self {
+            InputSource::Resources => fs::read_to_string(input_path(&resources_dir(), day)),
+            InputSource::File(path) => fs::read_to_string(path),
+            InputSource::Stdin => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+                Ok(input)
+            }
+            InputSource::Inline(input) => Ok(input.clone()),
+        }
+    }
+}
+
+impl Display for InputSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputSource::Resources => write!(f, "{}/inputN", resources_dir().display()),
+            InputSource::File(path) => write!(f, "{}", path.display()),
+            InputSource::Stdin => write!(f, "stdin"),
+            InputSource::Inline(_) => write!(f, "inline input"),
+        }
+    }
+}
+
+/// Directory of the inputs, taken from [`RESOURCES_DIR_VAR`] when it is set.
+pub fn resources_dir() -> PathBuf {
+    env::var_os(RESOURCES_DIR_VAR)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_RESOURCES_DIR))
+}
+
+pub fn input_path(resources_dir: &Path, day: u8) -> PathBuf {
+    resources_dir.join(format!("input{day}"))
+}
+
+/// Cleans up an input as saved by editors: the byte order mark is dropped, line endings
+/// become `\n` and the trailing blank lines are removed, as well as the trailing newline.
+///
+/// With [`Layout::Free`] the whitespace at the end of every line is removed too.
+pub fn normalize(input: &str, layout: Layout) -> String {
+    let input = input.strip_prefix('\u{feff}').unwrap_or(input);
+    let mut lines: Vec<&str> = input
+        .split('\n')
+        .map(|line| line.strip_suffix('\r').unwrap_or(line))
+        .map(|line| match layout {
+            Layout::Free => line.trim_end(),
+            Layout::Aligned => line,
+        })
+        .collect();
+    while lines.last().is_some_and(|line| line.trim().is_empty()) {
+        lines.pop();
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::path::{Path, PathBuf};
+
+    use super::{InputSource, Layout, input_path, normalize};
+
+    #[test]
+    fn sources() {
+        assert_eq!(InputSource::from_arg(None), InputSource::Resources);
+        assert_eq!(InputSource::from_arg(Some("-")), InputSource::Stdin);
+        assert_eq!(
+            InputSource::from_arg(Some("data/in.txt")),
+            InputSource::File(PathBuf::from("data/in.txt"))
+        );
+        assert_eq!(
+            input_path(Path::new("/tmp/inputs"), 9),
+            PathBuf::from("/tmp/inputs/input9")
+        );
+
+        let inline = InputSource::Inline("1-2".to_string());
+        assert_eq!(
+            inline.read(2).expect("inline input is always readable"),
+            "1-2"
+        );
+        assert!(
+            InputSource::File(PathBuf::from("does/not/exist"))
+                .read(1)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn normalization() {
+        let raw = "\u{feff}L68 \r\nL30\r\n\r\n  \n";
+        assert_eq!(normalize(raw, Layout::Free), "L68\nL30");
+        assert_eq!(normalize(raw, Layout::Aligned), "L68 \nL30");
+        assert_eq!(normalize(" 1 \n23 \n", Layout::Aligned), " 1 \n23 ");
+        assert_eq!(normalize("", Layout::Free), "");
+    }
+}
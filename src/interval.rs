@@ -0,0 +1,154 @@
+use std::iter::Sum;
+use std::ops::{Range, Sub};
+
+/// Set of values stored as sorted, disjoint and non adjacent half-open ranges.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    ranges: Vec<Range<T>>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        IntervalSet { ranges: Vec::new() }
+    }
+}
+
+impl<T: Copy + Ord> IntervalSet<T> {
+    pub fn new() -> Self {
+        IntervalSet::default()
+    }
+
+    /// Adds the values of the range, merging it with the ranges it overlaps or touches.
+    pub fn insert(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+        // the ranges from `first` to `last` excluded overlap or touch the new one
+        let first = self.ranges.partition_point(|r| r.end < range.start);
+        let last = self.ranges.partition_point(|r| r.start <= range.end);
+        let merged = if first < last {
+            self.ranges[first].start.min(range.start)..self.ranges[last - 1].end.max(range.end)
+        } else {
+            range
+        };
+        self.ranges.splice(first..last, [merged]);
+    }
+
+    /// Adds every value of the other set.
+    pub fn merge(&mut self, other: &IntervalSet<T>) {
+        other.ranges.iter().for_each(|r| self.insert(r.clone()));
+    }
+
+    /// Binary search of the range holding the value.
+    pub fn contains(&self, value: T) -> bool {
+        let i = self.ranges.partition_point(|r| r.end <= value);
+        self.ranges.get(i).is_some_and(|r| r.start <= value)
+    }
+
+    /// The ranges of the set, sorted.
+    pub fn ranges(&self) -> &[Range<T>] {
+        &self.ranges
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    pub fn union(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut res = self.clone();
+        res.merge(other);
+        res
+    }
+
+    pub fn intersection(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut ranges = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while let (Some(a), Some(b)) = (self.ranges.get(i), other.ranges.get(j)) {
+            let common = a.start.max(b.start)..a.end.min(b.end);
+            if !common.is_empty() {
+                ranges.push(common);
+            }
+            // the range ending first cannot meet any other range of the other set
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        IntervalSet { ranges }
+    }
+
+    /// Values of this set which are not in the other one.
+    pub fn difference(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut ranges = Vec::new();
+        let mut j = 0;
+        for range in &self.ranges {
+            let mut start = range.start;
+            // skip the ranges ending before this one
+            while other.ranges.get(j).is_some_and(|r| r.end <= start) {
+                j += 1;
+            }
+            let mut k = j;
+            while let Some(removed) = other.ranges.get(k).filter(|r| r.start < range.end) {
+                if start < removed.start {
+                    ranges.push(start..removed.start);
+                }
+                start = start.max(removed.end);
+                k += 1;
+            }
+            if start < range.end {
+                ranges.push(start..range.end);
+            }
+        }
+        IntervalSet { ranges }
+    }
+}
+
+impl<T: Copy + Ord + Sub<Output = T> + Sum> IntervalSet<T> {
+    /// Number of values in the set.
+    pub fn total_len(&self) -> T {
+        self.ranges.iter().map(|r| r.end - r.start).sum()
+    }
+}
+
+impl<T: Copy + Ord> FromIterator<Range<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        let mut res = IntervalSet::new();
+        iter.into_iter().for_each(|r| res.insert(r));
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::IntervalSet;
+
+    #[test]
+    fn insert() {
+        let mut set: IntervalSet<u64> = [10..15, 0..3, 20..25].into_iter().collect();
+        assert_eq!(set.ranges(), [0..3, 10..15, 20..25]);
+        set.insert(3..5);
+        set.insert(12..21);
+        set.insert(7..7);
+        assert_eq!(set.ranges(), [0..5, 10..25]);
+        assert_eq!(set.total_len(), 20);
+        assert!(set.contains(0));
+        assert!(set.contains(24));
+        assert!(!set.contains(5));
+        assert!(!set.contains(25));
+        assert!(!IntervalSet::new().contains(0u64));
+    }
+
+    #[test]
+    fn operations() {
+        let a: IntervalSet<u64> = [0..10, 20..30].into_iter().collect();
+        let b: IntervalSet<u64> = [5..8, 9..22, 28..40].into_iter().collect();
+        assert_eq!(a.union(&b).ranges(), vec![0..40]);
+        assert_eq!(a.intersection(&b).ranges(), [5..8, 9..10, 20..22, 28..30]);
+        assert_eq!(a.difference(&b).ranges(), [0..5, 8..9, 22..28]);
+        assert_eq!(b.difference(&a).ranges(), [10..20, 30..40]);
+        assert!(a.difference(&a).is_empty());
+        assert_eq!(a.intersection(&IntervalSet::new()), IntervalSet::new());
+    }
+}
@@ -0,0 +1,41 @@
+//! Advent of Code 2025 solutions.
+//!
+//! Each `dayN` module exposes its parsers, data structures and part functions,
+//! and a `DayN` type implementing [`solution::Solution`]. The [`registry`]
+//! gathers all of them for the command line.
+
+pub mod answers;
+pub mod bench;
+pub mod compression;
+pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+pub mod error;
+pub mod examples;
+pub mod fetch;
+pub mod geometry;
+pub mod graph;
+pub mod grid;
+pub mod http;
+pub mod input;
+pub mod interval;
+pub mod output;
+pub mod registry;
+pub mod runner;
+pub mod scaffold;
+pub mod solution;
+pub mod submit;
+pub mod svg;
+#[cfg(test)]
+mod testing;
+pub mod union_find;
+pub mod watch;
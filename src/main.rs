@@ -1,49 +1,524 @@
 use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::time::{SystemTime, UNIX_EPOCH};
 
-mod day1;
-mod day10;
-mod day11;
-mod day12;
-mod day2;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-mod day9;
+use aoc2025::answers::{self, Check};
+use aoc2025::bench;
+use aoc2025::day9::{self, Day9};
+use aoc2025::examples;
+use aoc2025::fetch::{self, Fetched};
+use aoc2025::geometry::Polygon;
+use aoc2025::http::{SiteConfig, UreqClient};
+use aoc2025::input::{self, InputSource};
+use aoc2025::output::{self, Format};
+use aoc2025::registry;
+use aoc2025::registry::SOLUTIONS;
+use aoc2025::runner::{self, Outcome};
+use aoc2025::scaffold;
+use aoc2025::solution::{DynSolution, Part, Solution};
+use aoc2025::submit::{self, Verdict};
+use aoc2025::watch;
 
 #[derive(clap::Parser, Debug)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 struct Args {
-    #[arg(value_parser = clap::value_parser!(u8).range(1..))]
-    day: u8,
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[command(flatten)]
+    run: RunArgs,
+}
+
+#[derive(clap::Args, Debug)]
+struct RunArgs {
+    #[arg(required = true, value_parser = clap::value_parser!(u8).range(1..))]
+    day: Option<u8>,
+
+    /// Part of the puzzle to compute
+    #[arg(short, long, value_enum, default_value_t = Part::Both)]
+    part: Part,
+
+    /// Input file to use instead of `inputN` in the resources directory
+    /// (`AOC2025_RESOURCES`, `./resources` by default), `-` reads stdin
+    #[arg(short, long)]
+    input: Option<String>,
+
+    /// Puzzle input given directly on the command line
+    #[arg(long, conflicts_with = "input")]
+    input_string: Option<String>,
+
+    /// Output format of the answers and timings
+    #[arg(short, long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+
+    /// Run the examples of the puzzle statement and check their answers
+    #[arg(long, conflicts_with_all = ["input", "input_string"])]
+    example: bool,
+
+    /// Run the day again every time its source file or its input file changes
+    #[arg(long, conflicts_with_all = ["input_string", "example", "format"])]
+    watch: bool,
+
+    /// Root of the crate watched and run through cargo, the current directory by default
+    #[arg(long, requires = "watch", default_value = ".")]
+    root: PathBuf,
+
+    /// Solve the day and draw the input with the answers to an SVG file, only day 9 can
+    /// be drawn
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["example", "watch", "format", "part"])]
+    render: Option<PathBuf>,
+}
+
+#[derive(clap::Subcommand, Debug)]
+enum Command {
+    /// Run every implemented day and print a summary table
+    RunAll {
+        /// Part of the puzzles to compute
+        #[arg(short, long, value_enum, default_value_t = Part::Both)]
+        part: Part,
+
+        /// Output format of the answers and timings
+        #[arg(short, long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+    },
+    /// Time the parsing and the parts of a day, or of every day with an input
+    Bench {
+        #[arg(value_parser = clap::value_parser!(u8).range(1..))]
+        day: Option<u8>,
+
+        /// Part of the puzzles to time
+        #[arg(short, long, value_enum, default_value_t = Part::Both)]
+        part: Part,
+
+        /// Number of runs of each step
+        #[arg(short = 'n', long, default_value_t = 10)]
+        runs: usize,
+    },
+    /// Check the answers of the real inputs against the recorded ones
+    Verify {
+        #[arg(value_parser = clap::value_parser!(u8).range(1..))]
+        day: Option<u8>,
+
+        /// Answers file, `answers.toml` in the resources directory by default
+        #[arg(short, long)]
+        answers: Option<PathBuf>,
+
+        /// Skip the days whose input is missing instead of failing
+        #[arg(long)]
+        allow_missing: bool,
+    },
+    /// Download the input of a day to the resources directory, unless already there
+    Fetch {
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        /// Address of the puzzle site, `AOC_BASE_URL` or the official site by default
+        #[arg(long)]
+        base_url: Option<String>,
+    },
+    /// Compute the answer of a part and send it to the puzzle site
+    Submit {
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+
+        /// Address of the puzzle site, `AOC_BASE_URL` or the official site by default
+        #[arg(long)]
+        base_url: Option<String>,
+    },
+    /// Create `src/dayN.rs` from the template and register the day
+    NewDay {
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        /// Root of the crate, the current directory by default
+        #[arg(long, default_value = ".")]
+        root: PathBuf,
+    },
 }
 
 fn main() {
     let args = <Args as clap::Parser>::parse();
 
-    let dispatch = [
-        day1::run,
-        day2::run,
-        day3::run,
-        day4::run,
-        day5::run,
-        day6::run,
-        day7::run,
-        day8::run,
-        day9::run,
-        day10::run,
-        day11::run,
-        day12::run,
-    ];
-
-    let dispatch_index = args.day as usize - 1;
-    if dispatch_index < dispatch.len() {
-        println!("Running day {}", args.day);
-        let input = fs::read_to_string(format!("./resources/input{}", args.day))
-            .expect("Could not load the input file");
-        dispatch[dispatch_index](&input);
-    } else {
-        println!("Day {} not yet implemented !", args.day);
+    match args.command {
+        Some(Command::RunAll { part, format }) => {
+            println!("{}", output::render(format, &runner::run_all(part)));
+        }
+        Some(Command::Bench { day, part, runs }) => bench(day, part, runs),
+        Some(Command::Verify {
+            day,
+            answers,
+            allow_missing,
+        }) => verify(day, answers, allow_missing),
+        Some(Command::Fetch { day, base_url }) => fetch(day, base_url),
+        Some(Command::Submit {
+            day,
+            part,
+            base_url,
+        }) => submit(day, part, base_url),
+        Some(Command::NewDay { day, root }) => new_day(day, &root),
+        None => run(args.run),
+    }
+}
+
+fn run_examples(day: u8, solution: &dyn DynSolution, part: Part, format: Format) {
+    let runs: Vec<_> = examples::examples(day)
+        .filter_map(|example| {
+            let parts = example.parts(part)?;
+            Some((
+                example,
+                runner::run_input(day, solution, example.input, parts),
+            ))
+        })
+        .collect();
+    if runs.is_empty() {
+        eprintln!("No example of day {day} for the requested parts");
+        process::exit(1);
+    }
+    if format != Format::Text {
+        let runs: Vec<_> = runs.iter().map(|(_, run)| run.clone()).collect();
+        println!("{}", output::render(format, &runs));
+    }
+
+    let mut failures = 0;
+    for (i, (example, run)) in runs.iter().enumerate() {
+        let results = match &run.outcome {
+            Outcome::Solved(results) => results,
+            Outcome::Failed(reason) | Outcome::Skipped(reason) => {
+                failures += 1;
+                eprintln!("Example {} failed : {reason}", i + 1);
+                continue;
+            }
+        };
+        for (part, reason) in results.errors() {
+            failures += 1;
+            eprintln!("=> example {} part{part} failed : {reason}", i + 1);
+        }
+        let actual = results.answers();
+        for (part, check) in answers::check(&example.expected, &actual) {
+            match check {
+                Check::Match if format == Format::Text => {
+                    let answer = if part == 1 {
+                        &actual.part1
+                    } else {
+                        &actual.part2
+                    };
+                    if let Some(answer) = answer {
+                        println!("=> example {} part{part} : {answer} ok", i + 1);
+                    }
+                }
+                Check::Match | Check::Missing { .. } => {}
+                Check::Mismatch { expected, actual } => {
+                    failures += 1;
+                    eprintln!(
+                        "=> example {} part{part} : MISMATCH expected {expected}, got {actual}",
+                        i + 1
+                    );
+                }
+            }
+        }
+    }
+    if failures > 0 {
+        process::exit(1);
+    }
+}
+
+/// Site settings from the environment, the base url being overridden when given.
+fn site_config(base_url: Option<String>) -> SiteConfig {
+    let config = SiteConfig::from_env(&input::resources_dir());
+    match base_url {
+        Some(base_url) => SiteConfig {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            ..config
+        },
+        None => config,
+    }
+}
+
+fn fetch(day: u8, base_url: Option<String>) {
+    let config = site_config(base_url);
+    match fetch::fetch_input(
+        &UreqClient::default(),
+        &config,
+        &input::resources_dir(),
+        day,
+    ) {
+        Ok(Fetched::Cached(path)) => println!("Input already cached at {}", path.display()),
+        Ok(Fetched::Downloaded(path)) => println!("Input downloaded to {}", path.display()),
+        Err(e) => {
+            eprintln!("Could not fetch the input of day {day} : {e}");
+            process::exit(1);
+        }
+    }
+}
+
+fn submit(day: u8, part: u8, base_url: Option<String>) {
+    let Some(solution) = registry::solution(day) else {
+        println!("Day {} not yet implemented !", day);
+        process::exit(1);
+    };
+    let input = InputSource::Resources.read(day).unwrap_or_else(|e| {
+        eprintln!("Could not load the input of day {day} : {e}");
+        process::exit(1);
+    });
+    let answer = match part {
+        1 => solution.part1(&input),
+        _ => solution.part2(&input),
+    }
+    .unwrap_or_else(|e| {
+        eprintln!("Day {day} failed : {e}");
+        process::exit(1);
+    });
+
+    println!("Submitting {answer} for day {day} part {part}");
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("the clock is after the epoch")
+        .as_secs();
+    let history = submit::history_path(&input::resources_dir());
+    let config = site_config(base_url);
+    match submit::submit(
+        &UreqClient::default(),
+        &config,
+        &history,
+        day,
+        part,
+        &answer,
+        now,
+    ) {
+        Ok(attempt) => {
+            println!("=> {}", attempt.verdict);
+            if let Some(wait_until) = attempt.wait_until {
+                println!("Next answer possible in {}s", wait_until - now);
+            }
+            if attempt.verdict != Verdict::Right {
+                process::exit(1);
+            }
+        }
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    }
+}
+
+fn new_day(day: u8, root: &Path) {
+    match scaffold::new_day(root, day) {
+        Ok(paths) => paths
+            .iter()
+            .for_each(|path| println!("Wrote {}", path.display())),
+        Err(e) => {
+            eprintln!("Could not create day {day} : {e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Solves the day and draws the rectangles of its answers, each part being computed once.
+fn render(day: u8, input: &str, path: &Path) {
+    if day != 9 {
+        eprintln!("Day {day} cannot be rendered, only day 9 can");
+        process::exit(1);
+    }
+    let rendered = Day9::parse(&input::normalize(input, Day9::LAYOUT)).and_then(|tiles| {
+        let largest = day9::largest_rectangle(&tiles)?;
+        let polygon = Polygon::new(tiles)?;
+        let inscribed = day9::largest_inscribed_rectangle(&polygon)?;
+        let svg = day9::render(&polygon, &largest, &inscribed)?;
+        fs::write(path, svg)?;
+        Ok((largest, inscribed))
+    });
+    match rendered {
+        Ok((largest, inscribed)) => {
+            println!("=> part1 : {}", largest.area);
+            println!("=> part2 : {}", inscribed.area);
+            println!("Rendered to {}", path.display());
+        }
+        Err(e) => {
+            eprintln!("Could not render day {day} : {e}");
+            process::exit(1);
+        }
+    }
+}
+
+fn verify(day: Option<u8>, answers_path: Option<PathBuf>, allow_missing: bool) {
+    let answers_path =
+        answers_path.unwrap_or_else(|| answers::answers_path(&input::resources_dir()));
+    let content = fs::read_to_string(&answers_path).unwrap_or_else(|e| {
+        eprintln!(
+            "Could not load the answers from {} : {e}",
+            answers_path.display()
+        );
+        process::exit(1);
+    });
+    let recorded = answers::recorded_answers(&content).unwrap_or_else(|e| {
+        eprintln!(
+            "Could not parse the answers from {} : {e}",
+            answers_path.display()
+        );
+        process::exit(1);
+    });
+
+    if recorded.is_empty() {
+        println!("No recorded answers in {}", answers_path.display());
+    }
+
+    let mut failures = 0;
+    for (day, expected) in recorded
+        .iter()
+        .filter(|(d, _)| day.is_none_or(|day| **d == day))
+    {
+        let Some(solution) = registry::solution(*day) else {
+            println!("Day {day} : not yet implemented !");
+            continue;
+        };
+        let run = runner::run_day(*day, solution, &InputSource::Resources, Part::Both);
+        let results = match run.outcome {
+            Outcome::Solved(results) => results,
+            Outcome::Skipped(reason) if allow_missing => {
+                println!("Day {day} : skipped ({reason})");
+                continue;
+            }
+            // recorded answers that cannot be checked must not pass unnoticed
+            Outcome::Skipped(reason) => {
+                failures += 1;
+                eprintln!("Day {day} : SKIPPED ({reason})");
+                continue;
+            }
+            Outcome::Failed(reason) => {
+                failures += 1;
+                eprintln!("Day {day} : FAILED {reason}");
+                continue;
+            }
+        };
+        for (part, reason) in results.errors() {
+            failures += 1;
+            eprintln!("Day {day} part {part} : FAILED {reason}");
+        }
+        for (part, check) in answers::check(expected, &results.answers()) {
+            match check {
+                Check::Match => println!("Day {day} part {part} : ok"),
+                Check::Mismatch { expected, actual } => {
+                    failures += 1;
+                    eprintln!("Day {day} part {part} : MISMATCH expected {expected}, got {actual}");
+                }
+                // the error of the part was reported
+                Check::Missing { .. } if results.error(part).is_some() => {}
+                Check::Missing { expected } => {
+                    failures += 1;
+                    eprintln!("Day {day} part {part} : MISSING expected {expected}");
+                }
+            }
+        }
+    }
+
+    if failures > 0 {
+        eprintln!("{failures} answer(s) could not be verified");
+        process::exit(1);
+    }
+}
+
+fn bench(day: Option<u8>, part: Part, runs: usize) {
+    let days = match day {
+        Some(day) => vec![day],
+        None => (1..=SOLUTIONS.len() as u8).collect(),
+    };
+    for day in days {
+        let Some(solution) = registry::solution(day) else {
+            println!("Day {} not yet implemented !", day);
+            continue;
+        };
+        let input = match InputSource::Resources.read(day) {
+            Ok(input) => input,
+            Err(e) => {
+                println!("Day {day} skipped : {e}");
+                continue;
+            }
+        };
+        match solution.bench(&input, part, runs) {
+            Ok(samples) => println!("{}", bench::report(day, &samples)),
+            Err(e) => println!("Day {day} failed : {e}"),
+        }
+    }
+}
+
+fn run(args: RunArgs) {
+    let day = args.day.expect("clap requires the day");
+    let Some(solution) = registry::solution(day) else {
+        println!("Day {} not yet implemented !", day);
+        return;
+    };
+    if args.watch {
+        let input = match InputSource::from_arg(args.input.as_deref()) {
+            InputSource::File(path) => path,
+            InputSource::Stdin => {
+                eprintln!("Watching needs an input file, not stdin");
+                process::exit(1);
+            }
+            _ => input::input_path(&input::resources_dir(), day),
+        };
+        let source = watch::source_path(&args.root, day);
+        if !source.exists() {
+            eprintln!(
+                "No source of day {day} at {}, give the root of the crate with --root",
+                source.display()
+            );
+            process::exit(1);
+        }
+        // the day runs from the root of the crate
+        let input = std::path::absolute(&input).unwrap_or(input);
+        watch::watch(&args.root, day, args.part, &input);
+    }
+    if args.format == Format::Text {
+        println!("Running day {}", day);
+    }
+    if args.example {
+        return run_examples(day, solution, args.part, args.format);
+    }
+    let source = match args.input_string {
+        Some(input) => InputSource::Inline(input),
+        None => InputSource::from_arg(args.input.as_deref()),
+    };
+    let input = source.read(day).unwrap_or_else(|e| {
+        eprintln!("Could not load the input from {source} : {e}");
+        process::exit(1);
+    });
+    if let Some(path) = &args.render {
+        return render(day, &input, path);
+    }
+
+    let run = runner::run_input(day, solution, &input, args.part);
+    if args.format != Format::Text {
+        println!(
+            "{}",
+            output::render(args.format, std::slice::from_ref(&run))
+        );
+    }
+    match run.outcome {
+        Outcome::Solved(results) => {
+            if args.format == Format::Text {
+                let answers = results.answers();
+                if let Some(part1) = answers.part1 {
+                    println!("=> part1 : {}", part1);
+                }
+                if let Some(part2) = answers.part2 {
+                    println!("=> part2 : {}", part2);
+                }
+            }
+            let errors = results.errors();
+            for (part, reason) in &errors {
+                eprintln!("Day {day} part {part} failed : {reason}");
+            }
+            if !errors.is_empty() {
+                process::exit(1);
+            }
+        }
+        Outcome::Failed(reason) | Outcome::Skipped(reason) => {
+            eprintln!("Day {day} failed : {reason}");
+            process::exit(1);
+        }
     }
 }
@@ -0,0 +1,201 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::runner::{self, DayRun, Outcome};
+
+/// How the answers are printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum Format {
+    /// Human readable lines or table
+    #[default]
+    Text,
+    Json,
+    Csv,
+}
+
+/// One entry of the machine readable output: an answer or an error.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Record {
+    pub day: u8,
+    /// Missing when the day could not be parsed or run at all
+    pub part: Option<u8>,
+    pub answer: Option<String>,
+    pub parse_time_ns: Option<u64>,
+    pub solve_time_ns: Option<u64>,
+    pub error: Option<String>,
+}
+
+const CSV_HEADER: &str = "day,part,answer,parse_time_ns,solve_time_ns,error";
+
+fn nanos(duration: Duration) -> u64 {
+    duration.as_nanos() as u64
+}
+
+/// Flattens the runs into one record per requested part, holding its answer or its
+/// error, and one record per day that could not be parsed or run.
+pub fn records(runs: &[DayRun]) -> Vec<Record> {
+    runs.iter()
+        .flat_map(|run| {
+            let timings = &run.timings;
+            match &run.outcome {
+                Outcome::Solved(results) => [
+                    (1, &results.part1, timings.part1),
+                    (2, &results.part2, timings.part2),
+                ]
+                .into_iter()
+                .filter_map(|(part, result, elapsed)| {
+                    let result = result.as_ref()?;
+                    Some(Record {
+                        day: run.day,
+                        part: Some(part),
+                        answer: result.as_ref().ok().map(|a| a.to_string()),
+                        parse_time_ns: Some(nanos(timings.parse)),
+                        solve_time_ns: elapsed.map(nanos),
+                        error: result.as_ref().err().cloned(),
+                    })
+                })
+                .collect(),
+                Outcome::Failed(reason) => vec![Record {
+                    day: run.day,
+                    part: None,
+                    answer: None,
+                    parse_time_ns: Some(nanos(timings.parse)),
+                    solve_time_ns: None,
+                    error: Some(reason.clone()),
+                }],
+                Outcome::Skipped(reason) => vec![Record {
+                    day: run.day,
+                    part: None,
+                    answer: None,
+                    parse_time_ns: None,
+                    solve_time_ns: None,
+                    error: Some(format!("skipped ({reason})")),
+                }],
+            }
+        })
+        .collect()
+}
+
+/// Renders the runs in the given format, text being the summary table.
+pub fn render(format: Format, runs: &[DayRun]) -> String {
+    match format {
+        Format::Text => runner::summary_table(runs),
+        Format::Json => to_json(&records(runs)),
+        Format::Csv => to_csv(&records(runs)),
+    }
+}
+
+pub fn to_json(records: &[Record]) -> String {
+    serde_json::to_string_pretty(records).expect("records are always serializable")
+}
+
+pub fn to_csv(records: &[Record]) -> String {
+    let mut lines = vec![CSV_HEADER.to_string()];
+    lines.extend(records.iter().map(|r| {
+        [
+            r.day.to_string(),
+            optional(r.part),
+            r.answer.as_deref().map(csv_field).unwrap_or_default(),
+            optional(r.parse_time_ns),
+            optional(r.solve_time_ns),
+            r.error.as_deref().map(csv_field).unwrap_or_default(),
+        ]
+        .join(",")
+    }));
+    lines.join("\n")
+}
+
+fn optional<T: ToString>(value: Option<T>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_default()
+}
+
+/// Quotes a field holding a separator, a quote or a line break.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::time::Duration;
+
+    use crate::runner::{DayRun, Outcome};
+    use crate::solution::{Answer, PartResults, Timings};
+
+    use super::{Record, records, to_csv, to_json};
+
+    #[test]
+    fn formats() {
+        let runs = [
+            DayRun {
+                day: 1,
+                outcome: Outcome::Solved(PartResults {
+                    part1: Some(Ok(Answer::Unsigned(3))),
+                    part2: None,
+                }),
+                elapsed: Duration::from_micros(5),
+                timings: Timings {
+                    parse: Duration::from_nanos(1200),
+                    part1: Some(Duration::from_nanos(3400)),
+                    part2: None,
+                },
+            },
+            DayRun {
+                day: 11,
+                outcome: Outcome::Solved(PartResults {
+                    part1: Some(Ok(Answer::Unsigned(5))),
+                    part2: Some(Err("invalid input : no node is named svr".to_string())),
+                }),
+                elapsed: Duration::from_micros(9),
+                timings: Timings {
+                    parse: Duration::from_nanos(500),
+                    part1: Some(Duration::from_nanos(700)),
+                    part2: Some(Duration::from_nanos(300)),
+                },
+            },
+            DayRun {
+                day: 6,
+                outcome: Outcome::Failed("parse error at line 1, column 15 : a, \"b\"".to_string()),
+                elapsed: Duration::from_micros(1),
+                timings: Timings {
+                    parse: Duration::from_nanos(800),
+                    ..Timings::default()
+                },
+            },
+        ];
+        let records = records(&runs);
+        assert_eq!(
+            records[0],
+            Record {
+                day: 1,
+                part: Some(1),
+                answer: Some("3".to_string()),
+                parse_time_ns: Some(1200),
+                solve_time_ns: Some(3400),
+                error: None,
+            }
+        );
+
+        assert_eq!(
+            to_csv(&records),
+            "day,part,answer,parse_time_ns,solve_time_ns,error
+1,1,3,1200,3400,
+11,1,5,500,700,
+11,2,,500,300,invalid input : no node is named svr
+6,,,800,,\"parse error at line 1, column 15 : a, \"\"b\"\"\""
+        );
+
+        let json: serde_json::Value =
+            serde_json::from_str(&to_json(&records)).expect("Could not read the json back");
+        assert_eq!(json[0]["answer"], "3");
+        assert_eq!(json[2]["part"], 2);
+        assert_eq!(json[2]["answer"], serde_json::Value::Null);
+        assert_eq!(json[3]["part"], serde_json::Value::Null);
+        assert_eq!(json[3]["parse_time_ns"], 800);
+    }
+}
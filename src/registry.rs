@@ -0,0 +1,55 @@
+use crate::solution::DynSolution;
+use crate::{day1, day2, day3, day4, day5, day6, day7, day8, day9, day10, day11, day12};
+
+/// Every implemented day, index 0 being day 1.
+pub static SOLUTIONS: [&dyn DynSolution; 12] = [
+    &day1::Day1,
+    &day2::Day2,
+    &day3::Day3,
+    &day4::Day4,
+    &day5::Day5,
+    &day6::Day6,
+    &day7::Day7,
+    &day8::Day8,
+    &day9::Day9,
+    &day10::Day10,
+    &day11::Day11,
+    &day12::Day12,
+];
+
+/// Returns the solution of the given day if it is implemented.
+pub fn solution(day: u8) -> Option<&'static dyn DynSolution> {
+    SOLUTIONS.get((day as usize).checked_sub(1)?).copied()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::solution::Part;
+
+    use super::solution;
+
+    #[test]
+    fn dispatch() {
+        assert!(solution(0).is_none());
+        assert!(solution(13).is_none());
+
+        let day1 = solution(1).expect("day 1 is implemented");
+        let answers = day1
+            .solve("L68\nL30\nR48", Part::Both)
+            .expect("Could not solve day 1");
+        assert_eq!(answers.part1.map(|a| a.to_string()).as_deref(), Some("1"));
+        assert_eq!(answers.part2.map(|a| a.to_string()).as_deref(), Some("2"));
+
+        let answers = day1
+            .solve("L68\nL30\nR48", Part::Two)
+            .expect("Could not solve day 1");
+        assert!(answers.part1.is_none());
+        assert_eq!(
+            day1.part1("L68\nL30\nR48")
+                .expect("Could not solve day 1")
+                .to_string(),
+            "1"
+        );
+    }
+}
@@ -0,0 +1,229 @@
+use std::io;
+use std::time::{Duration, Instant};
+
+use crate::input::InputSource;
+use crate::registry::SOLUTIONS;
+use crate::solution::{Answer, DynSolution, Part, PartResults, Timings};
+
+/// What happened when a day was run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    /// The input was parsed, each requested part has its answer or its error
+    Solved(PartResults<String>),
+    Skipped(String),
+    /// The input could not be parsed
+    Failed(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct DayRun {
+    pub day: u8,
+    pub outcome: Outcome,
+    pub elapsed: Duration,
+    pub timings: Timings,
+}
+
+/// Runs one day, a missing input file being reported as skipped.
+///
+/// Parsing errors are reported as failures, solving errors are kept with their part.
+pub fn run_day(day: u8, solution: &dyn DynSolution, source: &InputSource, part: Part) -> DayRun {
+    let input = match source.read(day) {
+        Ok(input) => input,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            return DayRun {
+                day,
+                outcome: Outcome::Skipped("no input".to_string()),
+                elapsed: Duration::ZERO,
+                timings: Timings::default(),
+            };
+        }
+        Err(e) => {
+            return DayRun {
+                day,
+                outcome: Outcome::Skipped(format!("could not read input : {e}")),
+                elapsed: Duration::ZERO,
+                timings: Timings::default(),
+            };
+        }
+    };
+    run_input(day, solution, &input, part)
+}
+
+/// Runs one day on an input already loaded.
+pub fn run_input(day: u8, solution: &dyn DynSolution, input: &str, part: Part) -> DayRun {
+    let start = Instant::now();
+    let (timings, result) = solution.solve_timed(input, part);
+    let outcome = match result {
+        Ok(results) => Outcome::Solved(results.map_err(|e| e.to_string())),
+        Err(e) => Outcome::Failed(e.to_string()),
+    };
+    DayRun {
+        day,
+        outcome,
+        elapsed: start.elapsed(),
+        timings,
+    }
+}
+
+/// Runs every registered day on its input from the resources directory.
+pub fn run_all(part: Part) -> Vec<DayRun> {
+    SOLUTIONS
+        .iter()
+        .enumerate()
+        .map(|(i, solution)| run_day(i as u8 + 1, *solution, &InputSource::Resources, part))
+        .collect()
+}
+
+/// Text of a part in the table: its answer, its error or nothing if it was not computed.
+fn cell(result: &Option<Result<Answer, String>>) -> String {
+    match result {
+        Some(Ok(answer)) => answer.to_string(),
+        Some(Err(reason)) => format!("failed ({reason})"),
+        None => String::new(),
+    }
+}
+
+/// Renders the runs as a text table, one line per day.
+pub fn summary_table(runs: &[DayRun]) -> String {
+    let rows: Vec<[String; 4]> = runs
+        .iter()
+        .map(|run| match &run.outcome {
+            Outcome::Solved(results) => [
+                run.day.to_string(),
+                cell(&results.part1),
+                cell(&results.part2),
+                format!("{:.2?}", run.elapsed),
+            ],
+            Outcome::Skipped(reason) => [
+                run.day.to_string(),
+                format!("skipped ({reason})"),
+                String::new(),
+                String::new(),
+            ],
+            Outcome::Failed(reason) => [
+                run.day.to_string(),
+                format!("failed ({reason})"),
+                String::new(),
+                format!("{:.2?}", run.elapsed),
+            ],
+        })
+        .collect();
+
+    let header = ["Day", "Part 1", "Part 2", "Time"].map(String::from);
+    // the padding of `format!` counts chars, and "µs" is 3 bytes for 2 chars
+    let mut widths = header.clone().map(|h| h.chars().count());
+    rows.iter().for_each(|row| {
+        row.iter()
+            .enumerate()
+            .for_each(|(i, cell)| widths[i] = widths[i].max(cell.chars().count()))
+    });
+
+    let line = |row: &[String; 4]| {
+        format!(
+            "{:>w0$} | {:<w1$} | {:<w2$} | {:>w3$}",
+            row[0],
+            row[1],
+            row[2],
+            row[3],
+            w0 = widths[0],
+            w1 = widths[1],
+            w2 = widths[2],
+            w3 = widths[3],
+        )
+        .trim_end()
+        .to_string()
+    };
+    let separator = widths
+        .iter()
+        .map(|w| "-".repeat(*w))
+        .collect::<Vec<_>>()
+        .join("-+-");
+
+    let mut table = vec![line(&header), separator];
+    table.extend(rows.iter().map(line));
+    table.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    use crate::day1::Day1;
+    use crate::day3::Day3;
+    use crate::input::InputSource;
+    use crate::solution::{Answer, Part, PartResults, Timings};
+
+    use super::{DayRun, Outcome, run_day, summary_table};
+
+    #[test]
+    fn missing_input_is_skipped() {
+        let source = InputSource::File(PathBuf::from("does/not/exist"));
+        let run = run_day(1, &Day1, &source, Part::Both);
+        assert_eq!(run.outcome, Outcome::Skipped("no input".to_string()));
+
+        let source = InputSource::Inline("L68\nL30\nR48".to_string());
+        let run = run_day(1, &Day1, &source, Part::One);
+        assert_eq!(
+            run.outcome,
+            Outcome::Solved(PartResults {
+                part1: Some(Ok(Answer::Unsigned(1))),
+                part2: None,
+            })
+        );
+
+        // banks of day 3 are too short for the 12 batteries of part 2, part 1 is kept
+        let source = InputSource::Inline("987".to_string());
+        let run = run_day(3, &Day3, &source, Part::Both);
+        match run.outcome {
+            Outcome::Solved(results) => {
+                assert_eq!(results.part1, Some(Ok(Answer::Unsigned(98))));
+                assert!(matches!(results.part2, Some(Err(_))));
+            }
+            outcome => panic!("unexpected outcome {outcome:?}"),
+        }
+
+        let source = InputSource::Inline("L68\nX30".to_string());
+        let run = run_day(1, &Day1, &source, Part::Both);
+        assert!(matches!(run.outcome, Outcome::Failed(_)));
+    }
+
+    #[test]
+    fn table() {
+        let runs = [
+            DayRun {
+                day: 1,
+                outcome: Outcome::Solved(PartResults {
+                    part1: Some(Ok(Answer::Unsigned(1))),
+                    part2: Some(Ok(Answer::Unsigned(6))),
+                }),
+                elapsed: Duration::from_millis(3),
+                timings: Timings::default(),
+            },
+            DayRun {
+                day: 2,
+                outcome: Outcome::Skipped("no input".to_string()),
+                elapsed: Duration::ZERO,
+                timings: Timings::default(),
+            },
+            DayRun {
+                day: 3,
+                outcome: Outcome::Solved(PartResults {
+                    part1: Some(Ok(Answer::Unsigned(357))),
+                    part2: Some(Err("no solution".to_string())),
+                }),
+                elapsed: Duration::from_micros(850),
+                timings: Timings::default(),
+            },
+        ];
+        assert_eq!(
+            summary_table(&runs),
+            "Day | Part 1             | Part 2               |     Time
+----+--------------------+----------------------+---------
+  1 | 1                  | 6                    |   3.00ms
+  2 | skipped (no input) |                      |
+  3 | 357                | failed (no solution) | 850.00µs"
+        );
+    }
+}
@@ -0,0 +1,240 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::error::{Error, Result};
+
+/// Source of a new day, `{{day}}` standing for its number.
+const TEMPLATE: &str = include_str!("../templates/day.rs");
+
+/// Source of the `dayN` module built from the template.
+pub fn day_source(day: u8) -> String {
+    TEMPLATE.replace("{{day}}", &day.to_string())
+}
+
+/// Adds `pub mod dayN;` to the modules of the crate root, keeping them sorted.
+pub fn register_module(lib: &str, day: u8) -> Result<String> {
+    let name = format!("day{day}");
+    let declaration = format!("pub mod {name};");
+    if lib.lines().any(|l| l == declaration) {
+        return Err(invalid_data(format!("{name} is already declared")));
+    }
+    let mut lines: Vec<&str> = lib.lines().collect();
+    let position = lines
+        .iter()
+        .position(|l| {
+            l.strip_prefix("pub mod ")
+                .and_then(|l| l.strip_suffix(';'))
+                .is_some_and(|module| module > name.as_str())
+        })
+        .or_else(|| {
+            lines
+                .iter()
+                .rposition(|l| l.starts_with("pub mod "))
+                .map(|i| i + 1)
+        })
+        .ok_or_else(|| invalid_data("no module declaration found".to_string()))?;
+    lines.insert(position, &declaration);
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Appends `dayN::DayN` to the solutions of the registry, which must end with day `N - 1`.
+pub fn register_solution(registry: &str, day: u8) -> Result<String> {
+    let array_start = registry
+        .find("pub static SOLUTIONS: [&dyn DynSolution; ")
+        .ok_or_else(|| invalid_data("the SOLUTIONS array was not found".to_string()))?;
+    let count_start = array_start + "pub static SOLUTIONS: [&dyn DynSolution; ".len();
+    let count_end = count_start
+        + registry[count_start..]
+            .find(']')
+            .ok_or_else(|| invalid_data("the SOLUTIONS array has no length".to_string()))?;
+    let count: u8 = registry[count_start..count_end]
+        .parse()
+        .map_err(|_| invalid_data("the SOLUTIONS length is not a number".to_string()))?;
+    if day != count + 1 {
+        return Err(invalid_data(format!(
+            "days are registered in order, the next one is day {}",
+            count + 1
+        )));
+    }
+    let array_end = count_end
+        + registry[count_end..]
+            .find("\n];")
+            .ok_or_else(|| invalid_data("the SOLUTIONS array is not closed".to_string()))?;
+
+    let imports_end = registry
+        .find("};")
+        .filter(|&i| registry[..i].contains("use crate::{"))
+        .ok_or_else(|| invalid_data("the days are not imported from the crate".to_string()))?;
+
+    Ok(format!(
+        "{}, day{day}{}{day}{}\n    &day{day}::Day{day},{}",
+        &registry[..imports_end].trim_end_matches([',', ' ', '\n']),
+        &registry[imports_end..count_start],
+        &registry[count_end..array_end],
+        &registry[array_end..]
+    ))
+}
+
+/// Adds an empty `DAYN` example to the examples, whose answers are the ones of the
+/// template so that `--example` and the generated test run from the start.
+pub fn register_example(examples: &str, day: u8) -> Result<String> {
+    let name = format!("DAY{day}");
+    if examples.contains(&format!("pub const {name}: &str")) {
+        return Err(invalid_data(format!("{name} is already declared")));
+    }
+    let array_start = examples
+        .find("pub static EXAMPLES: [Example; ")
+        .ok_or_else(|| invalid_data("the EXAMPLES array was not found".to_string()))?;
+    let count_start = array_start + "pub static EXAMPLES: [Example; ".len();
+    let count_end = count_start
+        + examples[count_start..]
+            .find(']')
+            .ok_or_else(|| invalid_data("the EXAMPLES array has no length".to_string()))?;
+    let count: usize = examples[count_start..count_end]
+        .parse()
+        .map_err(|_| invalid_data("the EXAMPLES length is not a number".to_string()))?;
+    let array_end = count_end
+        + examples[count_end..]
+            .find("\n];")
+            .ok_or_else(|| invalid_data("the EXAMPLES array is not closed".to_string()))?;
+
+    Ok(format!(
+        "{}pub const {name}: &str = \"\";\n\n{}{}{}
+    Example {{
+        day: {day},
+        input: {name},
+        expected: Answers {{
+            part1: Some(Answer::Unsigned(0)),
+            part2: Some(Answer::Unsigned(0)),
+        }},
+    }},{}",
+        &examples[..array_start],
+        &examples[array_start..count_start],
+        count + 1,
+        &examples[count_end..array_end],
+        &examples[array_end..]
+    ))
+}
+
+fn invalid_data(message: String) -> Error {
+    io::Error::new(io::ErrorKind::InvalidData, message).into()
+}
+
+/// Writes `src/dayN.rs` in the crate at `root` and registers the day and its example,
+/// returning the files written.
+pub fn new_day(root: &Path, day: u8) -> Result<Vec<PathBuf>> {
+    let src = root.join("src");
+    let day_path = src.join(format!("day{day}.rs"));
+    if day_path.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists", day_path.display()),
+        )
+        .into());
+    }
+
+    // everything is checked before the first write
+    let lib_path = src.join("lib.rs");
+    let registry_path = src.join("registry.rs");
+    let examples_path = src.join("examples.rs");
+    let lib = register_module(&fs::read_to_string(&lib_path)?, day)?;
+    let registry = register_solution(&fs::read_to_string(&registry_path)?, day)?;
+    let examples = register_example(&fs::read_to_string(&examples_path)?, day)?;
+
+    fs::write(&day_path, day_source(day))?;
+    fs::write(&lib_path, lib)?;
+    fs::write(&registry_path, registry)?;
+    fs::write(&examples_path, examples)?;
+    Ok(vec![day_path, lib_path, registry_path, examples_path])
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{day_source, register_example, register_module, register_solution};
+
+    #[test]
+    fn template() {
+        let source = day_source(13);
+        assert!(source.contains("pub struct Day13;"));
+        assert!(source.contains("impl Solution for Day13 {"));
+        assert!(!source.contains("{{day}}"));
+    }
+
+    #[test]
+    fn registration() {
+        let lib = "//! doc\n\npub mod answers;\npub mod day1;\npub mod day12;\npub mod day2;\npub mod error;\n";
+        assert_eq!(
+            register_module(lib, 13).expect("Could not register the module"),
+            "//! doc\n\npub mod answers;\npub mod day1;\npub mod day12;\npub mod day13;\npub mod day2;\npub mod error;\n"
+        );
+        assert!(register_module(lib, 12).is_err());
+
+        let registry = "use crate::solution::DynSolution;
+use crate::{day1, day2};
+
+pub static SOLUTIONS: [&dyn DynSolution; 2] = [
+    &day1::Day1,
+    &day2::Day2,
+];
+";
+        assert_eq!(
+            register_solution(registry, 3).expect("Could not register the solution"),
+            "use crate::solution::DynSolution;
+use crate::{day1, day2, day3};
+
+pub static SOLUTIONS: [&dyn DynSolution; 3] = [
+    &day1::Day1,
+    &day2::Day2,
+    &day3::Day3,
+];
+"
+        );
+        assert!(register_solution(registry, 4).is_err());
+    }
+
+    #[test]
+    fn example_registration() {
+        let examples = "pub const DAY1: &str = \"L68\";
+
+pub static EXAMPLES: [Example; 1] = [
+    Example {
+        day: 1,
+        input: DAY1,
+        expected: Answers {
+            part1: Some(Answer::Unsigned(3)),
+            part2: None,
+        },
+    },
+];
+";
+        assert_eq!(
+            register_example(examples, 2).expect("Could not register the example"),
+            "pub const DAY1: &str = \"L68\";
+
+pub const DAY2: &str = \"\";
+
+pub static EXAMPLES: [Example; 2] = [
+    Example {
+        day: 1,
+        input: DAY1,
+        expected: Answers {
+            part1: Some(Answer::Unsigned(3)),
+            part2: None,
+        },
+    },
+    Example {
+        day: 2,
+        input: DAY2,
+        expected: Answers {
+            part1: Some(Answer::Unsigned(0)),
+            part2: Some(Answer::Unsigned(0)),
+        },
+    },
+];
+"
+        );
+        assert!(register_example(examples, 1).is_err());
+    }
+}
@@ -0,0 +1,299 @@
+use std::fmt::Display;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use crate::bench::Samples;
+use crate::error::{Error, Result};
+use crate::input::{Layout, normalize};
+
+/// Answer produced by one part of a puzzle.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Unsigned(u64),
+    Signed(i64),
+    Text(String),
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Unsigned(n) => write!(f, "{n}"),
+            Answer::Signed(n) => write!(f, "{n}"),
+            Answer::Text(s) => write!(f, "{s}"),
+        }
+    }
+}
+
+impl From<u32> for Answer {
+    fn from(value: u32) -> Self {
+        Answer::Unsigned(value as u64)
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(value: u64) -> Self {
+        Answer::Unsigned(value)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        Answer::Unsigned(value as u64)
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(value: i64) -> Self {
+        Answer::Signed(value)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+/// A day of the calendar: the input is parsed once and shared by both parts.
+///
+/// The input given to `parse` is normalized according to `LAYOUT`.
+pub trait Solution {
+    type Input;
+    type Output1: Into<Answer>;
+    type Output2: Into<Answer>;
+
+    const LAYOUT: Layout = Layout::Free;
+
+    fn parse(input: &str) -> Result<Self::Input>;
+    fn part1(input: &Self::Input) -> Result<Self::Output1>;
+    fn part2(input: &Self::Input) -> Result<Self::Output2>;
+}
+
+/// Parts of a puzzle to compute.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum Part {
+    #[value(name = "1")]
+    One,
+    #[value(name = "2")]
+    Two,
+    #[default]
+    Both,
+}
+
+impl Part {
+    pub fn includes_part1(self) -> bool {
+        matches!(self, Part::One | Part::Both)
+    }
+
+    pub fn includes_part2(self) -> bool {
+        matches!(self, Part::Two | Part::Both)
+    }
+}
+
+/// Answers of the parts that were computed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub part1: Option<Answer>,
+    pub part2: Option<Answer>,
+}
+
+/// Result of each computed part, so that the error of one part does not hide the answer
+/// of the other. The parts that were not computed are `None`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartResults<E = Error> {
+    pub part1: Option<std::result::Result<Answer, E>>,
+    pub part2: Option<std::result::Result<Answer, E>>,
+}
+
+impl<E> Default for PartResults<E> {
+    fn default() -> Self {
+        PartResults {
+            part1: None,
+            part2: None,
+        }
+    }
+}
+
+impl<E> PartResults<E> {
+    fn parts(&self) -> [(u8, Option<&std::result::Result<Answer, E>>); 2] {
+        [(1, self.part1.as_ref()), (2, self.part2.as_ref())]
+    }
+
+    /// Answers of the parts that succeeded.
+    pub fn answers(&self) -> Answers {
+        let answer = |part: &Option<std::result::Result<Answer, E>>| {
+            part.as_ref().and_then(|r| r.as_ref().ok()).cloned()
+        };
+        Answers {
+            part1: answer(&self.part1),
+            part2: answer(&self.part2),
+        }
+    }
+
+    /// The parts that failed, with their error.
+    pub fn errors(&self) -> Vec<(u8, &E)> {
+        self.parts()
+            .into_iter()
+            .filter_map(|(part, result)| Some((part, result?.as_ref().err()?)))
+            .collect()
+    }
+
+    /// Error of the part, if it was computed and failed.
+    pub fn error(&self, part: u8) -> Option<&E> {
+        self.errors()
+            .into_iter()
+            .find_map(|(p, e)| (p == part).then_some(e))
+    }
+
+    /// The answers of the computed parts, or the error of the first one that failed.
+    pub fn into_answers(self) -> std::result::Result<Answers, E> {
+        Ok(Answers {
+            part1: self.part1.transpose()?,
+            part2: self.part2.transpose()?,
+        })
+    }
+
+    pub fn map_err<F>(self, f: impl Fn(E) -> F) -> PartResults<F> {
+        PartResults {
+            part1: self.part1.map(|r| r.map_err(&f)),
+            part2: self.part2.map(|r| r.map_err(&f)),
+        }
+    }
+}
+
+/// Time spent in each step of a run, the parts that were not computed having no time.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Timings {
+    pub parse: Duration,
+    pub part1: Option<Duration>,
+    pub part2: Option<Duration>,
+}
+
+/// Object safe view of a [`Solution`] so that all the days can live in the same table.
+pub trait DynSolution: Sync {
+    fn part1(&self, input: &str) -> Result<Answer> {
+        Ok(self
+            .solve(input, Part::One)?
+            .part1
+            .expect("part 1 was requested"))
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer> {
+        Ok(self
+            .solve(input, Part::Two)?
+            .part2
+            .expect("part 2 was requested"))
+    }
+
+    /// Parses the input once and computes the selected parts, failing if any of them fails.
+    fn solve(&self, input: &str, part: Part) -> Result<Answers> {
+        self.solve_timed(input, part).1?.into_answers()
+    }
+
+    /// Parses the input once and computes each selected part even if the other one
+    /// fails, timing the steps that were run. Only a parse error fails the whole run.
+    fn solve_timed(&self, input: &str, part: Part) -> (Timings, Result<PartResults>);
+
+    /// Times `runs` parsings of the input and `runs` computations of the selected parts.
+    fn bench(&self, input: &str, part: Part, runs: usize) -> Result<Samples>;
+}
+
+impl<S: Solution + Sync> DynSolution for S {
+    fn solve_timed(&self, input: &str, part: Part) -> (Timings, Result<PartResults>) {
+        let mut timings = Timings::default();
+        let start = Instant::now();
+        let parsed = S::parse(&normalize(input, S::LAYOUT));
+        timings.parse = start.elapsed();
+        let input = match parsed {
+            Ok(input) => input,
+            Err(e) => return (timings, Err(e)),
+        };
+
+        let mut results = PartResults::default();
+        if part.includes_part1() {
+            let start = Instant::now();
+            let answer = S::part1(&input);
+            timings.part1 = Some(start.elapsed());
+            results.part1 = Some(answer.map(Into::into));
+        }
+        if part.includes_part2() {
+            let start = Instant::now();
+            let answer = S::part2(&input);
+            timings.part2 = Some(start.elapsed());
+            results.part2 = Some(answer.map(Into::into));
+        }
+        (timings, Ok(results))
+    }
+
+    fn bench(&self, input: &str, part: Part, runs: usize) -> Result<Samples> {
+        let mut samples = Samples::default();
+        let mut parsed = None;
+        for _ in 0..runs {
+            let start = Instant::now();
+            parsed = Some(black_box(S::parse(&normalize(input, S::LAYOUT))?));
+            samples.parse.push(start.elapsed());
+        }
+        let Some(parsed) = parsed else {
+            return Ok(samples);
+        };
+
+        for _ in 0..runs {
+            if part.includes_part1() {
+                let start = Instant::now();
+                black_box(S::part1(&parsed)?);
+                samples.part1.push(start.elapsed());
+            }
+            if part.includes_part2() {
+                let start = Instant::now();
+                black_box(S::part2(&parsed)?);
+                samples.part2.push(start.elapsed());
+            }
+        }
+        Ok(samples)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{Answer, Answers, Part, PartResults};
+
+    #[test]
+    fn answers() {
+        assert_eq!(Answer::from(42u32), Answer::Unsigned(42));
+        assert_eq!(Answer::from(42usize), Answer::Unsigned(42));
+        assert_eq!(Answer::from(-3i64).to_string(), "-3");
+        assert_eq!(Answer::from("abc".to_string()).to_string(), "abc");
+    }
+
+    #[test]
+    fn parts() {
+        assert!(Part::One.includes_part1() && !Part::One.includes_part2());
+        assert!(!Part::Two.includes_part1() && Part::Two.includes_part2());
+        assert!(Part::Both.includes_part1() && Part::Both.includes_part2());
+    }
+
+    #[test]
+    fn part_results() {
+        let results: PartResults<String> = PartResults {
+            part1: Some(Ok(Answer::Unsigned(5))),
+            part2: Some(Err("no node is named svr".to_string())),
+        };
+        assert_eq!(
+            results.answers(),
+            Answers {
+                part1: Some(Answer::Unsigned(5)),
+                part2: None,
+            }
+        );
+        assert_eq!(
+            results.errors(),
+            vec![(2, &"no node is named svr".to_string())]
+        );
+        assert_eq!(results.error(1), None);
+        assert_eq!(
+            results.map_err(|e| e.len()).into_answers(),
+            Err("no node is named svr".len())
+        );
+    }
+}
@@ -0,0 +1,420 @@
+use std::fmt::Display;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, Result};
+use crate::http::{HttpClient, SiteConfig};
+use crate::solution::Answer;
+
+/// Name of the submission history in the resources directory.
+pub const HISTORY_FILE: &str = "submissions.json";
+
+pub fn history_path(resources_dir: &Path) -> PathBuf {
+    resources_dir.join(HISTORY_FILE)
+}
+
+/// What the puzzle site said about a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    Right,
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// The answer was not checked, an answer was given too recently
+    TooRecent,
+    /// The part was already solved
+    AlreadySolved,
+    /// The reply could not be understood
+    Unknown,
+    /// The answer is being sent, or its reply was lost
+    Pending,
+}
+
+impl Verdict {
+    /// The answer was checked against the solution.
+    fn is_checked(self) -> bool {
+        matches!(
+            self,
+            Verdict::Right | Verdict::Wrong | Verdict::TooHigh | Verdict::TooLow
+        )
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let text = match self {
+            Verdict::Right => "right",
+            Verdict::Wrong => "wrong",
+            Verdict::TooHigh => "too high",
+            Verdict::TooLow => "too low",
+            Verdict::TooRecent => "not checked, answered too recently",
+            Verdict::AlreadySolved => "not checked, already solved",
+            Verdict::Unknown => "unknown",
+            Verdict::Pending => "pending, no reply recorded",
+        };
+        write!(f, "{text}")
+    }
+}
+
+/// One answer sent to the puzzle site, times being seconds since the epoch.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Attempt {
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+    pub submitted_at: u64,
+    /// No answer can be sent before this time, whatever the puzzle
+    pub wait_until: Option<u64>,
+}
+
+/// Every answer sent so far, kept so that no answer known to be wrong is sent twice.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct History {
+    pub attempts: Vec<Attempt>,
+}
+
+impl History {
+    /// Loads the history, a missing file being an empty history.
+    pub fn load(path: &Path) -> Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(content) => serde_json::from_str(&content)
+                .map_err(|e| Error::Config(format!("invalid history in {} : {e}", path.display()))),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(History::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Writes the history, creating its directory if needed.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let content = serde_json::to_string_pretty(self).expect("the history is serializable");
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, content + "\n")?;
+        Ok(())
+    }
+
+    /// Refuses an answer already sent, out of the bounds given by the previous answers of
+    /// the part, sent while the reply to a previous one is unknown, or sent before the end
+    /// of the waiting time of any puzzle.
+    pub fn check(&self, day: u8, part: u8, answer: &str, now: u64) -> Result<()> {
+        let attempts: Vec<_> = self
+            .attempts
+            .iter()
+            .filter(|a| a.day == day && a.part == part)
+            .collect();
+
+        if let Some(pending) = attempts.iter().find(|a| a.verdict == Verdict::Pending) {
+            return Err(refused(format!(
+                "the reply to {} is unknown, check it on the site and update the history",
+                pending.answer
+            )));
+        }
+        if let Some(right) = attempts.iter().find(|a| a.verdict == Verdict::Right) {
+            return Err(refused(format!(
+                "day {day} part {part} was already solved with {}",
+                right.answer
+            )));
+        }
+        if let Some(previous) = attempts
+            .iter()
+            .find(|a| a.answer == answer && a.verdict.is_checked())
+        {
+            return Err(refused(format!(
+                "{answer} was already submitted and was {}",
+                previous.verdict
+            )));
+        }
+
+        if let Ok(value) = answer.parse::<i128>() {
+            let bound = |verdict| {
+                attempts
+                    .iter()
+                    .filter(move |a| a.verdict == verdict)
+                    .filter_map(|a| a.answer.parse::<i128>().ok())
+            };
+            if let Some(high) = bound(Verdict::TooHigh).filter(|h| value >= *h).min() {
+                return Err(refused(format!(
+                    "{answer} is not below {high} which is too high"
+                )));
+            }
+            if let Some(low) = bound(Verdict::TooLow).filter(|l| value <= *l).max() {
+                return Err(refused(format!(
+                    "{answer} is not above {low} which is too low"
+                )));
+            }
+        }
+
+        // the site makes the whole account wait, whatever the puzzle
+        if let Some(wait_until) = self
+            .attempts
+            .iter()
+            .filter_map(|a| a.wait_until)
+            .max()
+            .filter(|w| *w > now)
+        {
+            return Err(refused(format!(
+                "wait {}s before answering again",
+                wait_until - now
+            )));
+        }
+        Ok(())
+    }
+}
+
+fn refused(message: String) -> Error {
+    Error::SubmissionRefused(message)
+}
+
+/// Reads the verdict and the waiting time in seconds from a reply of the puzzle site.
+pub fn parse_reply(body: &str) -> (Verdict, Option<u64>) {
+    let verdict = if body.contains("That's the right answer") {
+        Verdict::Right
+    } else if body.contains("answer is too high") {
+        Verdict::TooHigh
+    } else if body.contains("answer is too low") {
+        Verdict::TooLow
+    } else if body.contains("That's not the right answer") {
+        Verdict::Wrong
+    } else if body.contains("You gave an answer too recently") {
+        Verdict::TooRecent
+    } else if body.contains("Did you already complete it") {
+        Verdict::AlreadySolved
+    } else {
+        Verdict::Unknown
+    };
+    (verdict, wait_seconds(body))
+}
+
+/// Waiting time announced as `You have 1m 5s left to wait` or `Please wait one minute`.
+fn wait_seconds(body: &str) -> Option<u64> {
+    if let Some(end) = body.find(" left to wait") {
+        let start = body[..end].rfind("You have ")? + "You have ".len();
+        return body[start..end]
+            .split_whitespace()
+            .map(|token| {
+                let unit = token.chars().last()?;
+                let value: u64 = token[..token.len() - unit.len_utf8()].parse().ok()?;
+                match unit {
+                    'h' => Some(value * 3600),
+                    'm' => Some(value * 60),
+                    's' => Some(value),
+                    _ => None,
+                }
+            })
+            .sum();
+    }
+
+    let start = body.find("lease wait ")? + "lease wait ".len();
+    let mut words = body[start..].split_whitespace();
+    let count = match words.next()? {
+        "one" | "a" => 1,
+        "two" => 2,
+        "five" => 5,
+        "ten" => 10,
+        number => number.parse().ok()?,
+    };
+    let unit = match words.next()? {
+        w if w.starts_with("minute") => 60,
+        w if w.starts_with("second") => 1,
+        _ => return None,
+    };
+    Some(count * unit)
+}
+
+/// Sends the answer of a part unless the history refuses it, then records the attempt.
+pub fn submit(
+    client: &dyn HttpClient,
+    config: &SiteConfig,
+    history_path: &Path,
+    day: u8,
+    part: u8,
+    answer: &Answer,
+    now: u64,
+) -> Result<Attempt> {
+    let answer = answer.to_string();
+    let mut history = History::load(history_path)?;
+    history.check(day, part, &answer, now)?;
+    let session = config.session()?;
+
+    // the answer is recorded before it is sent, so that it is guarded against even if
+    // its reply is lost
+    history.attempts.push(Attempt {
+        day,
+        part,
+        answer: answer.clone(),
+        verdict: Verdict::Pending,
+        submitted_at: now,
+        wait_until: None,
+    });
+    history.save(history_path)?;
+
+    let url = format!("{}/answer", config.day_url(day));
+    let level = part.to_string();
+    let body = client.post_form(
+        &url,
+        session,
+        &[("level", level.as_str()), ("answer", answer.as_str())],
+    )?;
+    let (verdict, wait) = parse_reply(&body);
+
+    let attempt = history.attempts.last_mut().expect("the attempt was pushed");
+    attempt.verdict = verdict;
+    attempt.wait_until = wait.map(|w| now + w);
+    let attempt = attempt.clone();
+    history.save(history_path)?;
+    Ok(attempt)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::error::Error;
+    use crate::solution::Answer;
+    use crate::testing::{StubSite, TempDir};
+
+    use super::{Attempt, History, Verdict, parse_reply, submit};
+
+    fn attempt(answer: &str, verdict: Verdict, wait_until: Option<u64>) -> Attempt {
+        Attempt {
+            day: 1,
+            part: 1,
+            answer: answer.to_string(),
+            verdict,
+            submitted_at: 0,
+            wait_until,
+        }
+    }
+
+    #[test]
+    fn replies() {
+        assert_eq!(
+            parse_reply(
+                "That's not the right answer; your answer is too high.  If you're stuck, [...] Please wait one minute before trying again."
+            ),
+            (Verdict::TooHigh, Some(60))
+        );
+        assert_eq!(
+            parse_reply(
+                "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 33s left to wait."
+            ),
+            (Verdict::TooRecent, Some(93))
+        );
+        assert_eq!(
+            parse_reply("That's the right answer!  You are one gold star closer."),
+            (Verdict::Right, None)
+        );
+        assert_eq!(
+            parse_reply(
+                "You don't seem to be solving the right level.  Did you already complete it?"
+            ),
+            (Verdict::AlreadySolved, None)
+        );
+    }
+
+    #[test]
+    fn guards() {
+        let history = History {
+            attempts: vec![
+                attempt("500", Verdict::TooHigh, Some(160)),
+                attempt("100", Verdict::TooLow, None),
+                attempt("300", Verdict::TooRecent, None),
+            ],
+        };
+        assert!(history.check(1, 1, "500", 200).is_err());
+        assert!(history.check(1, 1, "600", 200).is_err());
+        assert!(history.check(1, 1, "99", 200).is_err());
+        assert!(history.check(1, 1, "300", 100).is_err());
+        assert!(history.check(1, 1, "300", 200).is_ok());
+        assert!(history.check(1, 2, "600", 200).is_ok());
+        // the waiting time holds for every puzzle
+        assert!(history.check(1, 2, "600", 100).is_err());
+        assert!(history.check(4, 2, "600", 100).is_err());
+
+        let solved = History {
+            attempts: vec![attempt("42", Verdict::Right, None)],
+        };
+        assert!(matches!(
+            solved.check(1, 1, "43", 0),
+            Err(Error::SubmissionRefused(_))
+        ));
+    }
+
+    const TOO_LOW: &str = "That's not the right answer; your answer is too low.";
+
+    #[test]
+    fn history() {
+        let dir = TempDir::new("submit-history");
+        let path = dir.path().join("submissions.json");
+        let site = StubSite::replying(TOO_LOW);
+        let config = StubSite::config();
+
+        let attempt = submit(&site, &config, &path, 1, 1, &Answer::Unsigned(12), 10)
+            .expect("Could not submit");
+        assert_eq!(attempt.verdict, Verdict::TooLow);
+        assert!(submit(&site, &config, &path, 1, 1, &Answer::Unsigned(12), 20).is_err());
+        assert!(submit(&site, &config, &path, 1, 1, &Answer::Unsigned(8), 20).is_err());
+        assert_eq!(
+            *site.requests.borrow(),
+            vec![(
+                "http://localhost:8080/day/1/answer".to_string(),
+                vec![
+                    ("level".to_string(), "1".to_string()),
+                    ("answer".to_string(), "12".to_string())
+                ]
+            )]
+        );
+        assert_eq!(
+            History::load(&path)
+                .expect("Could not load the history")
+                .attempts,
+            vec![attempt]
+        );
+    }
+
+    #[test]
+    fn history_directory() {
+        let dir = TempDir::new("submit-directory");
+        let site = StubSite::replying(TOO_LOW);
+        let config = StubSite::config();
+
+        // the directory of the history is created
+        let path = dir.path().join("resources").join("submissions.json");
+        submit(&site, &config, &path, 1, 1, &Answer::Unsigned(12), 10).expect("Could not submit");
+        assert!(path.exists());
+
+        // nothing is posted when the history cannot be written
+        let blocked = path.join("submissions.json");
+        assert!(submit(&site, &config, &blocked, 1, 1, &Answer::Unsigned(12), 10).is_err());
+        assert_eq!(site.urls().len(), 1);
+    }
+
+    #[test]
+    fn lost_reply() {
+        let dir = TempDir::new("submit-lost-reply");
+        let path = dir.path().join("submissions.json");
+        let site = StubSite::down();
+        let config = StubSite::config();
+
+        assert!(submit(&site, &config, &path, 1, 1, &Answer::Unsigned(12), 10).is_err());
+        assert_eq!(site.urls().len(), 1);
+        let history = History::load(&path).expect("Could not load the history");
+        assert_eq!(
+            history.attempts,
+            vec![Attempt {
+                submitted_at: 10,
+                ..attempt("12", Verdict::Pending, None)
+            }]
+        );
+        assert!(matches!(
+            history.check(1, 1, "13", 20),
+            Err(Error::SubmissionRefused(_))
+        ));
+        assert!(history.check(1, 2, "13", 20).is_ok());
+    }
+}
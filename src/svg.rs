@@ -0,0 +1,70 @@
+use std::fmt::Display;
+
+/// SVG document made of plain shapes, enough to draw the puzzles.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Svg {
+    /// Area of the drawing shown, as `min_x min_y width height`
+    view_box: (u64, u64, u64, u64),
+    elements: Vec<String>,
+}
+
+impl Svg {
+    /// Empty drawing showing the area from `min_x, min_y`, `width` wide and `height` high.
+    pub fn new(min_x: u64, min_y: u64, width: u64, height: u64) -> Self {
+        Svg {
+            view_box: (min_x, min_y, width, height),
+            elements: Vec::new(),
+        }
+    }
+
+    /// Adds a rectangle, `style` being the attributes of its fill and stroke.
+    pub fn rect(&mut self, x: u64, y: u64, width: u64, height: u64, style: &str) {
+        self.elements.push(format!(
+            r#"<rect x="{x}" y="{y}" width="{width}" height="{height}" {style}/>"#
+        ));
+    }
+
+    /// Adds a closed polygon through the points.
+    pub fn polygon(&mut self, points: &[(f64, f64)], style: &str) {
+        let points: Vec<_> = points.iter().map(|(x, y)| format!("{x},{y}")).collect();
+        self.elements.push(format!(
+            r#"<polygon points="{}" {style}/>"#,
+            points.join(" ")
+        ));
+    }
+}
+
+impl Display for Svg {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (x, y, width, height) = self.view_box;
+        writeln!(
+            f,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="{x} {y} {width} {height}">"#
+        )?;
+        for element in &self.elements {
+            writeln!(f, "  {element}")?;
+        }
+        writeln!(f, "</svg>")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::Svg;
+
+    #[test]
+    fn document() {
+        let mut svg = Svg::new(0, 0, 10, 5);
+        svg.rect(1, 1, 3, 2, r#"fill="red""#);
+        svg.polygon(&[(0.5, 0.5), (4.5, 0.5), (4.5, 2.0)], r#"fill="none""#);
+        assert_eq!(
+            svg.to_string(),
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 10 5">
+  <rect x="1" y="1" width="3" height="2" fill="red"/>
+  <polygon points="0.5,0.5 4.5,0.5 4.5,2" fill="none"/>
+</svg>
+"#
+        );
+    }
+}
@@ -0,0 +1,104 @@
+//! Fixtures shared by the tests of several modules.
+
+use std::cell::RefCell;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+
+use crate::error::{Error, Result};
+use crate::http::{HttpClient, SiteConfig};
+
+/// Session expected by [`StubSite`].
+pub const SESSION: &str = "abc";
+
+/// Directory of the temp dir owned by one test, removed when dropped even if the test
+/// fails.
+pub struct TempDir {
+    path: PathBuf,
+}
+
+impl TempDir {
+    /// Empty directory, `name` telling the tests apart as they run in parallel.
+    pub fn new(name: &str) -> Self {
+        let path = env::temp_dir().join(format!("aoc2025-{name}-{}", process::id()));
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).expect("Could not create the temp dir");
+        TempDir { path }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
+
+/// Url of a request with its form, empty unless it is a POST.
+pub type Request = (String, Vec<(String, String)>);
+
+/// Puzzle site giving the same reply to every request, and recording the requests.
+pub struct StubSite {
+    /// `None` for a site that never replies
+    reply: Option<String>,
+    pub requests: RefCell<Vec<Request>>,
+}
+
+impl StubSite {
+    pub fn replying(body: &str) -> Self {
+        StubSite {
+            reply: Some(body.to_string()),
+            requests: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Site receiving the requests but whose replies are lost.
+    pub fn down() -> Self {
+        StubSite {
+            reply: None,
+            requests: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Configuration pointing at the stub.
+    pub fn config() -> SiteConfig {
+        SiteConfig {
+            base_url: "http://localhost:8080".to_string(),
+            session: Some(SESSION.to_string()),
+        }
+    }
+
+    pub fn urls(&self) -> Vec<String> {
+        self.requests
+            .borrow()
+            .iter()
+            .map(|(url, _)| url.clone())
+            .collect()
+    }
+
+    fn reply(&self, url: &str, session: &str, form: &[(&str, &str)]) -> Result<String> {
+        assert_eq!(session, SESSION);
+        let form = form
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        self.requests.borrow_mut().push((url.to_string(), form));
+        self.reply
+            .clone()
+            .ok_or_else(|| Error::Http("connection reset".to_string()))
+    }
+}
+
+impl HttpClient for StubSite {
+    fn get(&self, url: &str, session: &str) -> Result<String> {
+        self.reply(url, session, &[])
+    }
+
+    fn post_form(&self, url: &str, session: &str, form: &[(&str, &str)]) -> Result<String> {
+        self.reply(url, session, form)
+    }
+}
@@ -0,0 +1,169 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use clap::ValueEnum;
+
+use crate::error::{Error, Result};
+use crate::output::Record;
+use crate::solution::Part;
+
+/// Delay between two checks of the watched files.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Source file of a day in the crate at `root`.
+pub fn source_path(root: &Path, day: u8) -> PathBuf {
+    root.join("src").join(format!("day{day}.rs"))
+}
+
+/// Modification times of the files, `None` for the missing ones.
+fn snapshot(paths: &[PathBuf]) -> Vec<Option<SystemTime>> {
+    paths
+        .iter()
+        .map(|p| fs::metadata(p).and_then(|m| m.modified()).ok())
+        .collect()
+}
+
+/// Builds and runs the day through cargo, so that changes of its source are taken into
+/// account, and reads back the records of the run.
+pub fn run_with_cargo(root: &Path, day: u8, part: Part, input: &Path) -> Result<Vec<Record>> {
+    let cargo = env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    let part = part
+        .to_possible_value()
+        .expect("every part has a value")
+        .get_name()
+        .to_string();
+    let output = Command::new(cargo)
+        .current_dir(root)
+        .args(["run", "--quiet", "--", &day.to_string(), "--part", &part])
+        .args(["--format", "json", "--input"])
+        .arg(input)
+        .output()?;
+
+    // a failing day still prints its records, a failing build prints nothing
+    records(&String::from_utf8_lossy(&output.stdout)).ok_or_else(|| {
+        Error::Build(format!(
+            "the run produced no answer :\n{}",
+            String::from_utf8_lossy(&output.stderr).trim_end()
+        ))
+    })
+}
+
+/// The records printed by a run, the other lines it printed around them being skipped.
+fn records(stdout: &str) -> Option<Vec<Record>> {
+    // the records are an array starting a line, the first one to parse is kept
+    stdout
+        .match_indices('[')
+        .filter(|(i, _)| *i == 0 || stdout[..*i].ends_with('\n'))
+        .find_map(|(i, _)| {
+            serde_json::Deserializer::from_str(&stdout[i..])
+                .into_iter::<Vec<Record>>()
+                .next()?
+                .ok()
+        })
+}
+
+/// Answer of a record, or its error.
+fn outcome(record: &Record) -> String {
+    match (&record.answer, &record.error) {
+        (Some(answer), _) => answer.clone(),
+        (None, Some(error)) => format!("error ({error})"),
+        (None, None) => "no answer".to_string(),
+    }
+}
+
+/// Describes each part of the current run, next to its answer of the previous run.
+pub fn diff(previous: &[Record], current: &[Record]) -> Vec<String> {
+    current
+        .iter()
+        .map(|record| {
+            let name = match record.part {
+                Some(part) => format!("part{part}"),
+                None => "day".to_string(),
+            };
+            let now = outcome(record);
+            match previous.iter().find(|r| r.part == record.part).map(outcome) {
+                Some(before) if before == now => format!("=> {name} : {now} (unchanged)"),
+                Some(before) => format!("=> {name} : {now} (was {before})"),
+                None => format!("=> {name} : {now} (new)"),
+            }
+        })
+        .collect()
+}
+
+/// Runs the day, then runs it again every time its source or its input changes.
+pub fn watch(root: &Path, day: u8, part: Part, input: &Path) -> ! {
+    let paths = vec![source_path(root, day), input.to_path_buf()];
+    let mut previous = Vec::new();
+    let mut last_seen = None;
+    loop {
+        let current = snapshot(&paths);
+        if last_seen.as_ref() != Some(&current) {
+            last_seen = Some(current);
+            println!("Running day {day}");
+            match run_with_cargo(root, day, part, input) {
+                Ok(records) => {
+                    diff(&previous, &records)
+                        .iter()
+                        .for_each(|line| println!("{line}"));
+                    previous = records;
+                }
+                Err(e) => eprintln!("{e}"),
+            }
+            println!("Watching {} and {}", paths[0].display(), paths[1].display());
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::output::{Record, to_json};
+
+    use super::{diff, records};
+
+    fn record(part: u8, answer: Option<&str>, error: Option<&str>) -> Record {
+        Record {
+            day: 9,
+            part: Some(part),
+            answer: answer.map(String::from),
+            parse_time_ns: Some(10),
+            solve_time_ns: Some(20),
+            error: error.map(String::from),
+        }
+    }
+
+    #[test]
+    fn changes() {
+        let first = [record(1, Some("50"), None), record(2, Some("24"), None)];
+        assert_eq!(
+            diff(&[], &first),
+            vec!["=> part1 : 50 (new)", "=> part2 : 24 (new)"]
+        );
+
+        let second = [
+            record(1, Some("50"), None),
+            record(2, None, Some("no solution")),
+        ];
+        assert_eq!(
+            diff(&first, &second),
+            vec![
+                "=> part1 : 50 (unchanged)",
+                "=> part2 : error (no solution) (was 24)"
+            ]
+        );
+    }
+
+    #[test]
+    fn noisy_output() {
+        let json = to_json(&[record(1, Some("50"), None)]);
+        let stdout = format!("[debug] 3 trees left\n{json}\n[debug] done\n");
+        assert_eq!(records(&stdout), Some(vec![record(1, Some("50"), None)]));
+        assert_eq!(records(&json), Some(vec![record(1, Some("50"), None)]));
+        assert_eq!(records("[debug] nothing\n"), None);
+    }
+}
@@ -0,0 +1,65 @@
+use nom::{
+    IResult, Parser,
+    character::complete::{char, not_line_ending},
+    combinator::map,
+    multi::separated_list1,
+};
+
+use crate::error::{Result, parse_all};
+use crate::solution::Solution;
+
+pub fn part1(lines: &[String]) -> u64 {
+    0
+}
+
+pub fn part2(lines: &[String]) -> u64 {
+    0
+}
+
+pub struct Day{{day}};
+
+impl Solution for Day{{day}} {
+    type Input = Vec<String>;
+    type Output1 = u64;
+    type Output2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_all(input, lines)
+    }
+
+    fn part1(lines: &Self::Input) -> Result<u64> {
+        Ok(part1(lines))
+    }
+
+    fn part2(lines: &Self::Input) -> Result<u64> {
+        Ok(part2(lines))
+    }
+}
+
+// === PARSERS ===
+
+pub fn lines(input: &str) -> IResult<&str, Vec<String>> {
+    separated_list1(char('\n'), map(not_line_ending, String::from)).parse(input)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::examples::examples;
+    use crate::solution::{DynSolution, Part};
+
+    use super::Day{{day}};
+
+    #[test]
+    fn example_data() {
+        let mut examples = examples({{day}}).peekable();
+        assert!(examples.peek().is_some(), "day {{day}} has no example");
+        for example in examples {
+            let parts = example.parts(Part::Both).expect("the example has answers");
+            let answers = Day{{day}}
+                .solve(example.input, parts)
+                .expect("Could not solve the example");
+            assert_eq!(answers, example.expected);
+        }
+    }
+}
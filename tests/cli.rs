@@ -0,0 +1,51 @@
+use std::env;
+use std::fs;
+use std::process::{self, Command, Output};
+
+use aoc2025::examples::DAY12;
+use aoc2025::input::RESOURCES_DIR_VAR;
+use aoc2025::output::Record;
+
+/// Runs the binary with the given resources directory.
+fn run(args: &[&str], resources: &str) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_aoc2025"))
+        .args(args)
+        .env(RESOURCES_DIR_VAR, resources)
+        .output()
+        .expect("Could not run the binary")
+}
+
+/// Runs the binary, returning what it printed on stdout.
+fn stdout(args: &[&str]) -> String {
+    let output = run(args, "resources");
+    String::from_utf8(output.stdout).expect("stdout is not utf-8")
+}
+
+#[test]
+fn json_holds_only_records() {
+    let json = stdout(&["12", "--format", "json", "--input-string", DAY12]);
+    let records: Vec<Record> = serde_json::from_str(&json).expect("Could not parse the json");
+    let parts: Vec<_> = records.iter().map(|r| (r.day, r.part)).collect();
+    assert_eq!(parts, vec![(12, Some(1)), (12, Some(2))]);
+    assert!(records.iter().all(|r| r.error.is_none()));
+}
+
+#[test]
+fn verify_missing_input() {
+    let dir = env::temp_dir().join(format!("aoc2025-verify-{}", process::id()));
+    fs::create_dir_all(&dir).expect("Could not create the resources directory");
+    fs::write(dir.join("answers.toml"), "[day1]\npart1 = 3\n")
+        .expect("Could not write the answers");
+    let resources = dir.to_str().expect("the temp dir is utf-8");
+
+    let output = run(&["verify"], resources);
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+    assert!(
+        run(&["verify", "--allow-missing"], resources)
+            .status
+            .success()
+    );
+
+    fs::remove_dir_all(&dir).expect("Could not remove the resources directory");
+}